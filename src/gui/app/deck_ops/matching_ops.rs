@@ -0,0 +1,115 @@
+// src/gui/app/deck_ops/matching_ops.rs
+
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+
+use crate::gui::app::MorflashGui;
+use crate::model::Direction;
+use crate::srs::log::{self, ReviewSource};
+use crate::srs::matching::{self, MatchingRound, PairOutcome};
+use crate::srs::ReviewLogEntry;
+
+impl MorflashGui {
+    /// Matching mode: deal the next round from the session queue. With
+    /// the queue empty there is no round (nor current card), and the Study
+    /// screen moves on to the Completion screen.
+    pub(crate) fn start_matching_round(&mut self, now: DateTime<Utc>) {
+        let pairs = self
+            .options_state
+            .study
+            .matching_pairs
+            .clamp(matching::MIN_PAIRS, matching::MAX_PAIRS);
+
+        let mut dealt = Vec::with_capacity(pairs);
+        while dealt.len() < pairs {
+            let Some(key) = self.session.next(now) else {
+                break;
+            };
+            let (card_id, direction) = Direction::split_key(key);
+            if let Some(card) = self.cards.iter().find(|c| c.id == card_id) {
+                dealt.push((key, direction.orient(card)));
+            }
+        }
+
+        self.matching = (!dealt.is_empty()).then(|| MatchingRound::new(dealt, &mut self.rng));
+        self.card_shown_at = Some(Instant::now());
+    }
+
+    /// Matching mode: the learner picked a term and/or a definition.
+    pub(crate) fn handle_matching_pick(&mut self, term: Option<usize>, definition: Option<usize>) {
+        let Some(round) = self.matching.as_mut() else {
+            return;
+        };
+        if round.is_finished() {
+            return;
+        }
+
+        let outcomes = [
+            term.and_then(|card| round.select_term(card)),
+            definition.and_then(|card| round.select_definition(card)),
+        ];
+        let finished = round.is_finished();
+
+        for outcome in outcomes.into_iter().flatten() {
+            if let Some(ref sm) = self.sound {
+                if self.options_state.global.sound_enabled {
+                    sm.play(match outcome {
+                        PairOutcome::Matched => "correct",
+                        PairOutcome::Mismatched => "wrong",
+                    });
+                }
+            }
+        }
+
+        if finished {
+            self.finish_matching_round(Utc::now());
+        }
+    }
+
+    /// Score the finished round and feed each card's result to the
+    /// scheduler as a low-weight signal (logged as such), then move on to
+    /// the next round shortly (see `handle_auto_advance`).
+    fn finish_matching_round(&mut self, now: DateTime<Utc>) {
+        let Some(round) = self.matching.as_ref() else {
+            return;
+        };
+        let secs = self
+            .card_shown_at
+            .map_or(0.0, |t| t.elapsed().as_secs_f32());
+        self.matching_score.add_round(round, secs);
+
+        let deck = self.selected_deck_name.as_deref().unwrap_or_default();
+        let per_card_secs = secs / round.cards.len() as f32;
+        for (key, rating) in round.ratings() {
+            let Some(state) = self.states.get(&key) else {
+                continue;
+            };
+            let updated = matching::weighted_review(
+                self.scheduler.as_ref(),
+                state,
+                rating,
+                matching::SIGNAL_WEIGHT,
+                now,
+            );
+            let entry = ReviewLogEntry {
+                source: ReviewSource::Matching,
+                ..ReviewLogEntry::new(deck, state, &updated, rating, None, per_card_secs, now)
+            };
+            if let Err(e) = log::append(&self.review_log_path, &entry) {
+                eprintln!("MorFlash: failed to write review log: {e:#}");
+            }
+            self.states.insert(key, updated);
+        }
+        self.save_progress();
+
+        self.reviewed_count = (self.reviewed_count + round.cards.len()).min(self.total_cards);
+        self.feedback = format!(
+            "Round cleared in {secs:.0} s with {} mistake(s)!",
+            round.total_mistakes()
+        );
+        self.pending_advance = true;
+        self.last_answer_time = Some(now);
+        self.save_session();
+    }
+}
@@ -0,0 +1,26 @@
+// src/gui/app/deck_ops/media_ops.rs
+
+use std::path::{Path, PathBuf};
+
+use crate::gui::app::MorflashGui;
+use crate::gui::media::{self, MediaKind};
+
+impl MorflashGui {
+    /// The current card's media file (resolved against the deck's folder)
+    /// and what kind it is. Files the study screen can't use are skipped.
+    pub(crate) fn current_media(&self) -> Option<(MediaKind, PathBuf)> {
+        let media = self.current_card.as_ref()?.media.as_deref()?;
+        let path = media::resolve(self.deck_path.as_deref().and_then(Path::parent), media);
+        MediaKind::of(&path).map(|kind| (kind, path))
+    }
+
+    /// Play the current card's audio, if it has any.
+    pub(crate) fn play_card_audio(&mut self) {
+        let Some((MediaKind::Audio, path)) = self.current_media() else {
+            return;
+        };
+        if let Some(sm) = self.sound.as_mut() {
+            sm.play_file(&path);
+        }
+    }
+}
@@ -3,5 +3,9 @@
 pub mod builder_ops;
 pub mod export_ops;
 pub mod import_ops;
+pub mod matching_ops;
+pub mod media_ops;
+pub mod quiz_ops;
 pub mod review_ops;
+pub mod session_ops;
 pub mod study_ops;
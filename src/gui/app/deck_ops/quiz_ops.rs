@@ -0,0 +1,122 @@
+// src/gui/app/deck_ops/quiz_ops.rs
+
+use chrono::{DateTime, Utc};
+use rfd::FileDialog;
+
+use crate::gui::app::{MorflashGui, Screen};
+use crate::srs::cloze;
+use crate::srs::quiz::{self, Quiz, QuizKind};
+
+impl MorflashGui {
+    /// Start a sprint or exam over every studied, unsuspended card of the
+    /// open deck. The quiz takes over from the session queue until it is
+    /// finished (see `pick_next_card`).
+    pub(crate) fn start_quiz(&mut self, kind: QuizKind, now: DateTime<Utc>) {
+        let deck = self.selected_deck_name.clone().unwrap_or_default();
+        let keys: Vec<u64> = self
+            .studied_keys()
+            .into_iter()
+            .filter(|key| !self.states.get(key).is_some_and(|s| s.suspended))
+            .collect();
+
+        let study = &self.options_state.study;
+        let quiz = match kind {
+            QuizKind::Sprint => Quiz::sprint(&deck, keys, study.sprint_secs, now, &mut self.rng),
+            QuizKind::Exam => Quiz::exam(&deck, keys, study.exam_questions, now, &mut self.rng),
+        };
+        self.total_cards = quiz.length.unwrap_or(0);
+        self.reviewed_count = 0;
+        self.quiz = Some(quiz);
+    }
+
+    /// Record a quiz answer to the current card; nothing is rescheduled or
+    /// logged. A sprint shows whether it was right and moves on shortly;
+    /// an exam moves straight on without a word.
+    pub(crate) fn handle_quiz_answer(
+        &mut self,
+        given: &str,
+        correct: bool,
+        response_secs: f32,
+        now: DateTime<Utc>,
+    ) {
+        let (Some(key), Some(card), Some(quiz)) = (
+            self.current_key,
+            self.current_card.as_ref(),
+            self.quiz.as_mut(),
+        ) else {
+            return;
+        };
+
+        let prompt = match card.cloze {
+            Some(index) => cloze::prompt(&card.definition, index),
+            None => card.definition.clone(),
+        };
+        quiz.record(key, &prompt, &card.term, given, correct, response_secs);
+        self.reviewed_count = quiz.answers.len();
+
+        if quiz.kind == QuizKind::Exam {
+            self.pick_next_card(now);
+            return;
+        }
+
+        self.last_answer_correct = Some(correct);
+        self.correct_term = Some(card.term.clone());
+        self.wrong_term = (!correct).then(|| given.to_string());
+        self.feedback = if correct {
+            "Correct!".to_string()
+        } else {
+            format!("Wrong — the correct answer was '{}'.", card.term)
+        };
+        self.pending_advance = true;
+        self.last_answer_time = Some(now);
+    }
+
+    /// End the quiz and show its results.
+    pub(crate) fn finish_quiz(&mut self, now: DateTime<Utc>) {
+        let Some(quiz) = self.quiz.take() else {
+            return;
+        };
+
+        if let Some(ref sm) = self.sound {
+            if self.options_state.global.sound_enabled && !quiz.answers.is_empty() {
+                sm.play("complete");
+            }
+        }
+        self.quiz_report = Some(quiz.report(now));
+        self.current_key = None;
+        self.current_card = None;
+        self.options.clear();
+        self.pending_advance = false;
+        self.screen = Screen::QuizResults;
+    }
+
+    /// Ask where to save the quiz results, then write them as CSV or
+    /// JSON.
+    pub(crate) fn export_quiz_report(&mut self, csv: bool) {
+        let Some(report) = self.quiz_report.as_ref() else {
+            return;
+        };
+
+        let (name, ext) = if csv {
+            ("CSV", "csv")
+        } else {
+            ("JSON", "json")
+        };
+        let Some(path) = FileDialog::new()
+            .add_filter(name, &[ext])
+            .set_file_name(format!("{}.{ext}", report.file_stem()))
+            .save_file()
+        else {
+            return;
+        };
+        let path = path.with_extension(ext);
+
+        match quiz::save_report(&path, report) {
+            Ok(()) => self.notify(format!("Saved results to {}", path.display()), false),
+            Err(e) => {
+                eprintln!("MorFlash: failed to save quiz results: {e:#}");
+                self.notify(format!("Couldn't save results: {e}"), true);
+            }
+        }
+    }
+}
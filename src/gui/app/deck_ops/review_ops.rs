@@ -1,98 +1,164 @@
 // src/gui/app/review_ops.rs
 
-use chrono::{DateTime, Utc};
+use std::time::Instant;
 
+use chrono::{DateTime, Duration, Utc};
+
+use crate::gui::app::screens::options_screen::{DetailsVisibility, StudyMode};
+use crate::gui::app::screens::study_screen::CardAction;
 use crate::gui::app::MorflashGui;
+use crate::model::{Card, Direction, ReviewState};
+use crate::srs::{
+    day, log, typed, ChoiceBuilder, Rating, ReviewLogEntry, TypedGrader, TypedVerdict,
+};
 
 impl MorflashGui {
-    /// Pick the next card to show.
+    /// Pick the next card to show (or, in matching mode, deal the next
+    /// round).
     ///
-    /// Simple behavior:
-    /// - If there are no cards, nothing is shown.
-    /// - If we've already reviewed all cards, we're done (no current card).
-    /// - Otherwise, show cards[reviewed_count].
-    pub(crate) fn pick_next_card(&mut self, _now: DateTime<Utc>) {
+    /// Cards come from the session queue built in `load_deck` (plus any
+    /// learning cards requeued by `handle_answer`); once it's empty there
+    /// is no current card and the Study screen moves on to
+    /// the Completion screen. A sprint or exam draws from its quiz
+    /// instead, and shows its results once it runs out.
+    pub(crate) fn pick_next_card(&mut self, now: DateTime<Utc>) {
         // Whenever we move to a new card (or to "no card"), clear
         // the per-card visual/feedback state so highlights don't linger.
         self.feedback.clear();
         self.last_answer_correct = None;
         self.correct_term = None;
         self.wrong_term = None;
+        self.last_rating = None;
         self.pending_advance = false;
+        self.awaiting_continue = false;
+        self.revealed = false;
+        self.grade_preview = None;
+        self.typed_answer.clear();
+        self.typed_diff.clear();
 
-        // No cards at all.
-        if self.cards.is_empty() {
-            self.current_card_id = None;
+        if self.options_state.study.answer_mode() == StudyMode::Matching {
+            self.current_key = None;
+            self.current_card = None;
             self.options.clear();
+            self.start_matching_round(now);
             return;
         }
 
-        // We've already gone through every card once.
-        if self.reviewed_count >= self.total_cards {
-            // Signal "no more cards" so the Study screen can transition
-            // to the Completion screen.
-            self.current_card_id = None;
+        // Queue exhausted (or nothing was due): signal "no more cards"
+        // so the Study screen can transition to the Completion screen.
+        let next = match self.quiz.as_mut() {
+            Some(quiz) => quiz.next(&mut self.rng),
+            None => self.session.next(now),
+        };
+        let Some(key) = next else {
+            self.current_key = None;
+            self.current_card = None;
             self.options.clear();
+            self.finish_quiz(now);
             return;
-        }
+        };
 
-        // Use reviewed_count as the index into cards.
-        let idx = self.reviewed_count;
-        if let Some(card) = self.cards.get(idx) {
-            self.current_card_id = Some(card.id);
-        } else {
-            // Safety fallback: shouldn't happen, but don't crash.
-            self.current_card_id = None;
-        }
+        // Ask the card the way round its review key says.
+        let (card_id, direction) = Direction::split_key(key);
+        self.current_key = Some(key);
+        self.current_card = self
+            .cards
+            .iter()
+            .find(|c| c.id == card_id)
+            .map(|c| direction.orient(c));
+        self.card_shown_at = Some(Instant::now());
 
         // Rebuild multiple-choice options for the current card.
         self.rebuild_answer_options();
+        self.play_card_audio();
     }
 
-    /// Build multiple-choice options for the current card.
-    ///
-    /// Right now:
-    /// - Always includes the current card.
-    /// - Adds up to 3 other cards as simple distractors.
+    /// Build multiple-choice options for the current card: the answer
+    /// plus distractors from the whole deck (random or similar, per the
+    /// study options, and asked the same way round), shuffled.
     fn rebuild_answer_options(&mut self) {
         self.options.clear();
 
-        let Some(current_id) = self.current_card_id else {
+        let (Some(key), Some(current)) = (self.current_key, self.current_card.as_ref()) else {
             return;
         };
+        let (_, direction) = Direction::split_key(key);
+        let deck: Vec<Card> = self.cards.iter().map(|c| direction.orient(c)).collect();
 
-        // Include the current card first.
-        if let Some(current) = self.cards.iter().find(|c| c.id == current_id) {
-            self.options.push(current.clone());
-        }
-
-        // Add up to 3 other distinct cards as distractors.
-        for card in self.cards.iter() {
-            if self.options.len() >= 4 {
-                break;
-            }
-            if card.id != current_id {
-                self.options.push(card.clone());
-            }
-        }
+        let builder = ChoiceBuilder {
+            option_count: self.options_state.study.answer_count,
+            strategy: self.options_state.study.distractor_strategy,
+        };
+        let confused = self
+            .states
+            .get(&key)
+            .map_or(&[][..], |s| s.confused_with.as_slice());
+        self.options = builder.build(current, &deck, confused, &mut self.rng);
     }
 
-    /// Handle the user clicking an answer.
+    /// Handle the user clicking (or typing) an answer.
     ///
     /// - Updates feedback / correctness flags.
+    /// - Derives a `Rating` from correctness and response time; typed
+    ///   answers are graded per the study options and diffed against
+    ///   the term.
+    /// - Reschedules the card's `ReviewState` via the active scheduler,
+    ///   appends the answer to the review log and saves the deck's
+    ///   progress file.
     /// - Advances `reviewed_count`.
     /// - Schedules auto-advance (handled in `handle_auto_advance`).
+    /// - Snapshots the session so it can be resumed (`save_session`).
+    ///
+    /// In a sprint or exam the answer only goes into the quiz (see
+    /// `handle_quiz_answer`).
     pub(crate) fn handle_answer(&mut self, term: &str) {
         let now = Utc::now();
 
-        let Some(current_id) = self.current_card_id else {
+        let (Some(key), Some(current)) = (self.current_key, self.current_card.as_ref()) else {
             return;
         };
-        let Some(current) = self.cards.iter().find(|c| c.id == current_id) else {
-            return;
+
+        // Grade the answer from correctness + how long it took.
+        let response_secs = self
+            .card_shown_at
+            .map_or(0.0, |t| t.elapsed().as_secs_f32());
+        let study = &self.options_state.study;
+        let mode = study.answer_mode();
+        let verdict = match mode {
+            StudyMode::MultipleChoice | StudyMode::Flip | StudyMode::Matching
+                if term == current.term =>
+            {
+                TypedVerdict::Correct
+            }
+            StudyMode::MultipleChoice | StudyMode::Flip | StudyMode::Matching => {
+                TypedVerdict::Wrong
+            }
+            StudyMode::Typed => {
+                let grader = TypedGrader {
+                    mode: study.typed_grading,
+                    max_typos: study.max_typos,
+                };
+                self.typed_diff = typed::diff(term, &current.term, grader.mode);
+                grader.check(term, &current.term)
+            }
         };
+        let was_correct = verdict.is_accepted();
+        if self.quiz.is_some() {
+            self.handle_quiz_answer(term, was_correct, response_secs, now);
+            return;
+        }
 
-        let was_correct = term == current.term;
+        // A typo or a wrong typed answer is worth reading before moving on,
+        // and so are details revealed by answering.
+        self.awaiting_continue = (mode == StudyMode::Typed && verdict != TypedVerdict::Correct)
+            || (study.details == DetailsVisibility::AfterAnswer && current.has_details());
+        let rating = match mode {
+            StudyMode::Typed => {
+                verdict.rating(&study.auto_grade, response_secs, current.term.chars().count())
+            }
+            _ => study.auto_grade.rate(was_correct, response_secs),
+        };
+        self.last_rating = Some(rating);
 
         self.last_answer_correct = Some(was_correct);
         self.correct_term = Some(current.term.clone());
@@ -104,8 +170,14 @@ impl MorflashGui {
 
         // Simple text feedback.
         self.feedback.clear();
-        if was_correct {
-            self.feedback.push_str("Correct!");
+        if let TypedVerdict::Close { .. } = verdict {
+            self.feedback.push_str(&format!(
+                "Almost — it's spelled '{}'. ({})",
+                current.term,
+                rating.label()
+            ));
+        } else if was_correct {
+            self.feedback.push_str(&format!("Correct! ({})", rating.label()));
         } else {
             self.feedback.push_str(&format!(
                 "Wrong — the correct answer was '{}'.",
@@ -113,14 +185,147 @@ impl MorflashGui {
             ));
         }
 
+        // Only a picked option is another card's term worth avoiding.
+        let confused = (!was_correct && mode == StudyMode::MultipleChoice).then_some(term);
+        self.record_review(key, rating, Some(term), confused, response_secs, now);
+
+        // Tell the app to auto-advance in ~700 ms (handled in handle_auto_advance).
+        self.pending_advance = true;
+        self.last_answer_time = Some(now);
+        self.save_session();
+    }
+
+    /// Flip mode: the learner graded themselves after revealing the answer.
+    /// Nothing to read afterwards, so move straight on to the next card.
+    pub(crate) fn handle_self_grade(&mut self, rating: Rating) {
+        let now = Utc::now();
+
+        let Some(key) = self.current_key else {
+            return;
+        };
+        let response_secs = self
+            .card_shown_at
+            .map_or(0.0, |t| t.elapsed().as_secs_f32());
+
+        self.last_rating = Some(rating);
+        self.last_answer_correct = Some(rating.is_pass());
+        self.record_review(key, rating, None, None, response_secs, now);
+        self.pick_next_card(now);
+        self.save_session();
+    }
+
+    /// Flip mode: how long each grade (Again, Hard, Good, Easy) would
+    /// put the current card away for, before any interval fuzz.
+    pub(crate) fn grade_intervals(&self, now: DateTime<Utc>) -> Option<[Duration; 4]> {
+        let state = self.states.get(&self.current_key?)?;
+        Some(Rating::ALL.map(|r| self.scheduler.preview(state, r, now).next_review - now))
+    }
+
+    /// Reschedule the card direction `key` with `rating`, log the answer (`chosen` is what
+    /// the learner picked or typed, if anything), save progress and count
+    /// it as reviewed. A miss (`Again`) comes back later in the session
+    /// when learning until correct.
+    fn record_review(
+        &mut self,
+        key: u64,
+        rating: Rating,
+        chosen: Option<&str>,
+        confused: Option<&str>,
+        response_secs: f32,
+        now: DateTime<Utc>,
+    ) {
+        // A resumed session can hold a direction that wasn't studied when
+        // the deck was opened; it starts out new, as `progress::reconcile`
+        // would have made it.
+        let state = &*self
+            .states
+            .entry(key)
+            .or_insert_with(|| ReviewState::new(key, now));
+
+        // Reschedule the card with the derived rating.
+        let rollover = self.options_state.study.day_rollover_hour;
+        self.daily.roll_over(day::study_day(now, rollover));
+        self.daily.record(state, self.session.is_seen(key));
+
+        let mut updated = self.scheduler.review(state, rating, now);
+        if let Some(term) = confused {
+            updated.record_confusion(term);
+        }
+        if self.leech.check(&mut updated) {
+            self.session_leeches.push(key);
+        }
+
+        let missed = !rating.is_pass();
+        self.session.record_answer(key, !missed);
+        if missed && self.options_state.study.learn_until_correct && !updated.suspended {
+            self.session.reinsert(key, self.options_state.study.requeue_gap);
+            self.total_cards = self.session.total;
+        } else if self
+            .session
+            .requeue_if_learning(&updated, day::next_day_start(now, rollover))
+        {
+            // Cards still on a learning step come back later this study day.
+            self.total_cards = self.session.total;
+        }
+        let entry = ReviewLogEntry::new(
+            self.selected_deck_name.as_deref().unwrap_or_default(),
+            state,
+            &updated,
+            rating,
+            chosen.map(str::to_string),
+            response_secs,
+            now,
+        );
+        if let Err(e) = log::append(&self.review_log_path, &entry) {
+            eprintln!("MorFlash: failed to write review log: {e:#}");
+        }
+
+        self.states.insert(key, updated);
+        self.save_progress();
+
         // Move progress forward: this is what drives which card
         // `pick_next_card` will show next.
         if self.reviewed_count < self.total_cards {
             self.reviewed_count += 1;
         }
+    }
 
-        // Tell the app to auto-advance in ~700 ms (handled in handle_auto_advance).
-        self.pending_advance = true;
-        self.last_answer_time = Some(now);
+    /// Suspend, bury or flag the current card.
+    ///
+    /// Suspending or burying takes the card out of the rest of the session
+    /// and moves on; flagging just updates the card in place.
+    pub(crate) fn apply_card_action(&mut self, action: CardAction) {
+        let now = Utc::now();
+
+        let Some(key) = self.current_key else {
+            return;
+        };
+        let Some(state) = self.states.get_mut(&key) else {
+            return;
+        };
+
+        let skip = match action {
+            CardAction::Suspend => {
+                state.suspended = true;
+                true
+            }
+            CardAction::Bury => {
+                let rollover = self.options_state.study.day_rollover_hour;
+                state.buried_until = Some(day::next_day_start(now, rollover));
+                true
+            }
+            CardAction::Flag(flag) => {
+                state.flag = flag;
+                false
+            }
+        };
+        self.save_progress();
+
+        if skip {
+            self.session.skip(key, self.pending_advance);
+            self.total_cards = self.session.total;
+            self.pick_next_card(now);
+            self.save_session();
+        }
     }
 }
@@ -0,0 +1,119 @@
+// src/gui/app/deck_ops/session_ops.rs
+
+use chrono::Utc;
+
+use crate::gui::app::screens::options_screen::StudyMode;
+use crate::gui::app::MorflashGui;
+use crate::srs::session::{self, SessionSnapshot};
+
+impl MorflashGui {
+    /// Snapshot the study session so it can be resumed later (see
+    /// `srs::session`). Cards dealt but not answered yet go back to the
+    /// front of the saved queue. Quizzes aren't saved: they leave review
+    /// state alone.
+    pub(crate) fn save_session(&mut self) {
+        let Some(deck_path) = self.deck_path.clone() else {
+            return;
+        };
+        if self.quiz.is_some() {
+            return;
+        }
+
+        let unanswered: Vec<u64> = if self.options_state.study.answer_mode() == StudyMode::Matching
+        {
+            self.matching
+                .as_ref()
+                .filter(|round| !round.is_finished())
+                .map(|round| round.keys.clone())
+                .unwrap_or_default()
+        } else {
+            self.current_key
+                .filter(|_| !self.pending_advance)
+                .into_iter()
+                .collect()
+        };
+        let mut queue = self.session.clone();
+        queue.put_back(&unanswered);
+        if queue.is_empty() {
+            // Nothing left to study: the session is over.
+            self.clear_session();
+            return;
+        }
+
+        let snapshot = SessionSnapshot {
+            deck_path,
+            deck_fingerprint: self.deck_fingerprint,
+            saved_at: Utc::now(),
+            queue,
+            reviewed_count: self.reviewed_count,
+            total_cards: self.total_cards,
+            leeches: self.session_leeches.clone(),
+            matching_score: self.matching_score,
+        };
+        match session::save(&self.session_path, &snapshot) {
+            Ok(()) => self.resumable = Some(snapshot),
+            Err(e) => eprintln!("MorFlash: failed to save session: {e:#}"),
+        }
+    }
+
+    /// Forget the saved session if it is the open deck's (e.g. it was just
+    /// finished). Another deck's unfinished session is kept.
+    pub(crate) fn clear_session(&mut self) {
+        let ours = self
+            .resumable
+            .as_ref()
+            .is_some_and(|s| self.deck_path.as_ref() == Some(&s.deck_path));
+        if !ours {
+            return;
+        }
+
+        if let Err(e) = session::clear(&self.session_path) {
+            eprintln!("MorFlash: failed to remove saved session: {e:#}");
+        }
+        self.resumable = None;
+    }
+
+    /// The saved session, if there is one and its deck hasn't changed
+    /// since.
+    pub(crate) fn load_resumable_session(&self) -> Option<SessionSnapshot> {
+        let snapshot = match session::load(&self.session_path) {
+            Ok(snapshot) => snapshot?,
+            Err(e) => {
+                eprintln!("MorFlash: ignoring unreadable session: {e:#}");
+                return None;
+            }
+        };
+
+        match snapshot.deck_unchanged() {
+            Ok(true) => Some(snapshot),
+            Ok(false) => {
+                eprintln!(
+                    "MorFlash: {} changed since the last session; it can't be resumed",
+                    snapshot.deck_path.display()
+                );
+                None
+            }
+            Err(e) => {
+                eprintln!("MorFlash: can't resume the last session: {e:#}");
+                None
+            }
+        }
+    }
+
+    /// Pick the saved session up where it was left: open its deck, then
+    /// restore the queue and counters instead of starting afresh.
+    pub(crate) fn resume_session(&mut self) {
+        let Some(snapshot) = self.load_resumable_session() else {
+            self.resumable = None;
+            self.notify(
+                "The last session's deck has changed; it can't be resumed.".to_string(),
+                true,
+            );
+            return;
+        };
+
+        self.celebration_played = false;
+        let path = snapshot.deck_path.clone();
+        self.open_deck(&path, Some(snapshot));
+    }
+}
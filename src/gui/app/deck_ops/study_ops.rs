@@ -6,8 +6,11 @@ use std::path::{Path, PathBuf};
 
 use chrono::Utc;
 
+use crate::gui::app::screens::options_screen::StudyMode;
 use crate::gui::app::{MorflashGui, Screen};
 use crate::model::{Deck, ReviewState};
+use crate::srs::session::{self, SessionSnapshot};
+use crate::srs::{day, progress, FuzzedScheduler, SessionQueue, SteppedScheduler};
 
 impl MorflashGui {
     /// Rescan the `decks/` directory and refresh the in-memory list.
@@ -16,40 +19,191 @@ impl MorflashGui {
     }
 
     /// Load a deck file from disk and initialize SRS state for studying.
+    ///
+    /// Saved progress from `progress/<deck>-progress.json` is restored and
+    /// reconciled against the deck's current cards, and today's counts from
+    /// `progress/<deck>-today.json` limit how much the session contains.
     pub(crate) fn load_deck(&mut self, path: &Path) {
-        if let Ok(deck) = Deck::from_json_file(path) {
+        self.open_deck(path, None);
+    }
+
+    /// `load_deck`, or with `resume` carry on a saved session of the deck
+    /// instead of building a new one.
+    pub(crate) fn open_deck(&mut self, path: &Path, resume: Option<SessionSnapshot>) {
+        let loaded = Deck::from_json_file(path);
+        if let Err(e) = &loaded {
+            eprintln!("MorFlash: failed to open deck {}: {e:#}", path.display());
+            self.notify(format!("Couldn't open the deck: {e}"), true);
+        }
+        if let Ok(deck) = loaded {
+            // Don't lose anything from a deck that was already open.
+            self.save_progress();
+
             let cards = deck.cards;
             let now = Utc::now();
-            let mut state_map: HashMap<u64, ReviewState> = HashMap::new();
-
-            for card in &cards {
-                state_map.insert(card.id, ReviewState::new(card.id, now));
-            }
 
             self.selected_deck_name = path
                 .file_stem()
                 .and_then(|s| s.to_str())
                 .map(|s| s.to_string());
+            self.deck_path = Some(path.to_path_buf());
+            self.deck_fingerprint = fs::read(path).map_or(0, |bytes| session::fingerprint(&bytes));
+            self.media.clear();
+
+            let progress_dir = Path::new(progress::PROGRESS_DIR);
+            let progress_path = self
+                .selected_deck_name
+                .as_deref()
+                .map(|key| progress::progress_path(progress_dir, key));
+            let counts_path = self
+                .selected_deck_name
+                .as_deref()
+                .map(|key| progress::counts_path(progress_dir, key));
+
+            let mut state_map = match progress_path.as_deref().map(progress::load_progress) {
+                Some(Ok(states)) => states,
+                Some(Err(e)) => {
+                    eprintln!("MorFlash: ignoring unreadable progress: {e:#}");
+                    Default::default()
+                }
+                None => Default::default(),
+            };
+
+            let directions = self.options_state.study.directions.directions();
+            let report = progress::reconcile(&mut state_map, &cards, directions, now);
+            if report.removed > 0 {
+                eprintln!(
+                    "MorFlash: dropped progress for {} card(s) no longer in the deck",
+                    report.removed
+                );
+            }
+
+            // Per-deck scheduler / retention / fitted parameters / steps
+            // override, else the global choice.
+            let study = &self.options_state.study;
+            let kind = deck.srs.scheduler_or(study.scheduler);
+            let retention = deck.srs.retention_or(study.desired_retention);
+            let params = deck.srs.params_or(&study.scheduler_params);
+            let steps = deck.srs.steps_or(&study.learning_steps);
+            let stepped =
+                SteppedScheduler::new(kind.build_with_retention(retention, &params), steps);
+            self.scheduler = if study.interval_fuzz {
+                Box::new(FuzzedScheduler::seeded(Box::new(stepped), study.fuzz_seed))
+            } else {
+                Box::new(stepped)
+            };
+            self.daily_limits = deck.srs.limits_or(study.daily_limits);
+            self.leech = deck.srs.leech_or(study.leech);
+            self.deck_srs = deck.srs;
+
+            let mut daily = match counts_path.as_deref().map(progress::load_counts) {
+                Some(Ok(counts)) => counts,
+                Some(Err(e)) => {
+                    eprintln!("MorFlash: ignoring unreadable daily counts: {e:#}");
+                    Default::default()
+                }
+                None => Default::default(),
+            };
+            daily.roll_over(day::study_day(now, self.options_state.study.day_rollover_hour));
 
             self.cards = cards;
             self.states = state_map;
+            self.progress_path = progress_path;
+            self.daily = daily;
+            self.counts_path = counts_path;
+            if report.added > 0 || report.removed > 0 {
+                self.save_progress();
+            }
+
             self.feedback.clear();
-            self.current_card_id = None;
+            self.current_key = None;
+            self.current_card = None;
             self.options.clear();
             self.last_answer_correct = None;
             self.correct_term = None;
             self.wrong_term = None;
             self.pending_advance = false;
             self.last_answer_time = None;
+            self.session_leeches.clear();
+            self.matching = None;
+            self.matching_score = Default::default();
+            self.quiz = None;
 
-            self.total_cards = self.cards.len();
-            self.reviewed_count = 0;
+            if let Some(snapshot) = resume {
+                self.session = snapshot.queue;
+                self.total_cards = snapshot.total_cards;
+                self.reviewed_count = snapshot.reviewed_count;
+                self.session_leeches = snapshot.leeches;
+                self.matching_score = snapshot.matching_score;
+            } else {
+                // Only due cards (plus a few new ones) make it into the
+                // session, up to what's left of today's caps. Each studied
+                // direction of a card is queued on its own. Matching rounds
+                // can't introduce new cards (see `matching::weighted_review`),
+                // so those wait for another mode.
+                let keys = self.studied_keys();
+                let new_limit = if self.options_state.study.answer_mode() == StudyMode::Matching {
+                    0
+                } else {
+                    self.daily.new_remaining(&self.daily_limits)
+                };
+                self.session = SessionQueue::build(
+                    &keys,
+                    &self.states,
+                    self.scheduler.as_ref(),
+                    now,
+                    new_limit,
+                    self.daily.reviews_remaining(&self.daily_limits),
+                    self.options_state.study.separate_siblings,
+                );
+
+                self.total_cards = self.session.total;
+                self.reviewed_count = 0;
+
+                // Sprints and exams ask from the whole deck instead.
+                if let Some(kind) = self.options_state.study.session_kind.quiz() {
+                    self.start_quiz(kind, now);
+                }
+            }
 
             self.screen = Screen::Study;
             self.pick_next_card(now);
         }
     }
 
+    /// Review keys of every card in each direction it is studied in.
+    pub(crate) fn studied_keys(&self) -> Vec<u64> {
+        let directions = self.options_state.study.directions.directions();
+        self.cards
+            .iter()
+            .flat_map(|card| card.directions(directions).iter().map(|d| d.key(card.id)))
+            .collect()
+    }
+
+    /// Review states of the directions being studied (states of other
+    /// directions are kept on disk but left out of forecasts).
+    pub(crate) fn studied_states(&self) -> HashMap<u64, ReviewState> {
+        self.studied_keys()
+            .into_iter()
+            .filter_map(|key| self.states.get(&key).map(|state| (key, state.clone())))
+            .collect()
+    }
+
+    /// Write the current deck's review states and daily counts to disk.
+    pub(crate) fn save_progress(&self) {
+        if let Some(path) = self.progress_path.as_deref() {
+            if let Err(e) = progress::save_progress(path, &self.states) {
+                eprintln!("MorFlash: failed to save progress: {e:#}");
+            }
+        }
+
+        if let Some(path) = self.counts_path.as_deref() {
+            if let Err(e) = progress::save_counts(path, &self.daily) {
+                eprintln!("MorFlash: failed to save daily counts: {e:#}");
+            }
+        }
+    }
+
     /// Find all `.json` deck files in the given directory.
     pub(crate) fn load_all_deck_paths(dir: &str) -> anyhow::Result<Vec<PathBuf>> {
         let mut out = Vec::new();
@@ -3,9 +3,14 @@ use eframe::{
     egui::{self, ColorImage, TextureHandle, TextureOptions},
     App,
 };
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use rfd::FileDialog;
 use std::time::Instant;
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
 mod deck_ops;
 pub mod screens;
@@ -14,13 +19,30 @@ use screens::{
     completion_screen,
     deck_builder_screen,
     main_menu_screen,
+    matching_screen,
     options_screen,
+    quiz_results_screen,
     study_screen,
     controls_screen,     // ← REQUIRED, you were missing this
 };
 
-use crate::gui::{sound::SoundManager, theme::Theme};
-use crate::model::{Card, ReviewState};
+use crate::gui::{
+    media::{MediaCache, MediaKind},
+    sound::SoundManager,
+    theme::Theme,
+};
+use crate::model::{Card, Direction, ReviewState};
+use crate::srs::{
+    self,
+    matching::{MatchScore, MatchingRound},
+    quiz::{Quiz, QuizReport},
+    session::{self as study_session, SessionSnapshot},
+    typed::DiffChar,
+    DailyCounts, DailyLimits, DeckSrsSettings, LeechSettings, Rating, Scheduler, SessionQueue,
+};
+
+/// How many days the completion screen's workload forecast covers.
+const FORECAST_DAYS: usize = 14;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ScreenMode {
@@ -37,6 +59,7 @@ pub enum Screen {
     Study,
     Options,
     Completion,
+    QuizResults,
     DeckBuilder,
     Controls,
 }
@@ -63,13 +86,38 @@ pub struct MorflashGui {
     // ============================
     pub(crate) deck_paths: Vec<PathBuf>,
     pub(crate) selected_deck_name: Option<String>,
+    /// The open deck file (card media paths are relative to its folder),
+    /// and a fingerprint of its contents for the session snapshot.
+    pub(crate) deck_path: Option<PathBuf>,
+    pub(crate) deck_fingerprint: u64,
 
     // ============================
     // SRS state
     // ============================
     pub(crate) cards: Vec<Card>,
     pub(crate) states: HashMap<u64, ReviewState>,
-    pub(crate) current_card_id: Option<u64>,
+    /// Review key (card id + direction, see `Direction::key`) of the card
+    /// being shown, and that card as asked in its direction.
+    pub(crate) current_key: Option<u64>,
+    pub(crate) current_card: Option<Card>,
+    pub(crate) session: SessionQueue,
+    pub(crate) scheduler: Box<dyn Scheduler>,
+    pub(crate) deck_srs: DeckSrsSettings,
+    /// Where the open deck's progress is saved (`None` when no deck is open).
+    pub(crate) progress_path: Option<PathBuf>,
+    /// The open deck's caps, and what has been studied against them today.
+    pub(crate) daily_limits: DailyLimits,
+    pub(crate) daily: DailyCounts,
+    pub(crate) counts_path: Option<PathBuf>,
+    /// Every answer is appended here (shared by all decks).
+    pub(crate) review_log_path: PathBuf,
+    pub(crate) leech: LeechSettings,
+    /// Cards that became leeches during this session.
+    pub(crate) session_leeches: Vec<u64>,
+    /// Where the study session is snapshotted, and the snapshot the main
+    /// menu offers to resume.
+    pub(crate) session_path: PathBuf,
+    pub(crate) resumable: Option<SessionSnapshot>,
 
     // ============================
     // Multiple choice options & feedback
@@ -79,6 +127,30 @@ pub struct MorflashGui {
     pub(crate) last_answer_correct: Option<bool>,
     pub(crate) correct_term: Option<String>,
     pub(crate) wrong_term: Option<String>,
+    /// When the current card appeared (response time feeds the rating).
+    pub(crate) card_shown_at: Option<Instant>,
+    /// How the last answer was graded.
+    pub(crate) last_rating: Option<Rating>,
+    /// Shuffles answer options and picks distractors.
+    pub(crate) rng: StdRng,
+    /// Typed-answer mode: what's in the text box, and its diff against
+    /// the term once graded.
+    pub(crate) typed_answer: String,
+    pub(crate) typed_diff: Vec<DiffChar>,
+    /// The answer needs a closer look (e.g. a typo), so wait for the
+    /// learner to continue instead of auto-advancing.
+    pub(crate) awaiting_continue: bool,
+    /// Flip mode: the answer side of the current card is showing, and
+    /// the intervals under the grade buttons (worked out once per reveal).
+    pub(crate) revealed: bool,
+    pub(crate) grade_preview: Option<[chrono::Duration; 4]>,
+    /// Matching mode: the round being played, and the session's totals.
+    pub(crate) matching: Option<MatchingRound>,
+    pub(crate) matching_score: MatchScore,
+    /// Sprint / exam: the quiz being taken (review state is left alone
+    /// while it runs), and the results of the last one.
+    pub(crate) quiz: Option<Quiz>,
+    pub(crate) quiz_report: Option<QuizReport>,
 
     // ============================
     // Progress / auto-advance
@@ -120,6 +192,8 @@ pub struct MorflashGui {
     // UI textures
     // ============================
     pub(crate) mor_button_tex: Option<TextureHandle>,
+    /// Card images of the open deck.
+    pub(crate) media: MediaCache,
 
     // ============================
     // Screen-specific state
@@ -149,11 +223,26 @@ impl MorflashGui {
     // decks
     deck_paths,
     selected_deck_name: None,
+    deck_path: None,
+    deck_fingerprint: 0,
 
     // SRS
     cards: Vec::new(),
     states: HashMap::new(),
-    current_card_id: None,
+    current_key: None,
+    current_card: None,
+    session: SessionQueue::default(),
+    scheduler: options_state.study.scheduler.build(),
+    deck_srs: DeckSrsSettings::default(),
+    progress_path: None,
+    daily_limits: DailyLimits::default(),
+    daily: DailyCounts::default(),
+    counts_path: None,
+    review_log_path: srs::log::review_log_path(Path::new(srs::progress::PROGRESS_DIR)),
+    leech: options_state.study.leech,
+    session_leeches: Vec::new(),
+    session_path: study_session::session_path(Path::new(srs::progress::PROGRESS_DIR)),
+    resumable: None,
 
     // multiple choice
     options: Vec::new(),
@@ -161,6 +250,18 @@ impl MorflashGui {
     last_answer_correct: None,
     correct_term: None,
     wrong_term: None,
+    card_shown_at: None,
+    last_rating: None,
+    rng: StdRng::from_entropy(),
+    typed_answer: String::new(),
+    typed_diff: Vec::new(),
+    awaiting_continue: false,
+    revealed: false,
+    grade_preview: None,
+    matching: None,
+    matching_score: MatchScore::default(),
+    quiz: None,
+    quiz_report: None,
 
     // progress / auto-advance
     total_cards: 0,
@@ -186,6 +287,7 @@ impl MorflashGui {
 
     // textures
     mor_button_tex: None,
+    media: MediaCache::default(),
 
     // screen-specific state
     deck_builder_state: deck_builder_screen::DeckBuilderState::default(),
@@ -198,6 +300,7 @@ impl MorflashGui {
 
         app.configure_sounds_from_options();
         app.last_applied_sound_version = app.options_state.global.sound_version;
+        app.resumable = app.load_resumable_session();
         app
     }
 
@@ -212,7 +315,18 @@ fn play_main_menu_nav_sound(&self) {
 }
 
     fn trigger_main_menu_enter(&mut self) {
-    match self.main_menu_focus {
+    // "Resume session" sits above the other items when offered.
+    let focus = if self.resumable.is_some() {
+        if self.main_menu_focus == 0 {
+            self.resume_session();
+            return;
+        }
+        self.main_menu_focus - 1
+    } else {
+        self.main_menu_focus
+    };
+
+    match focus {
         // 0: Choose Deck – open file picker
         0 => {
             let mut dialog = FileDialog::new()
@@ -495,8 +609,10 @@ impl MorflashGui {
         }
 
         // We have 3 items: 0 = Choose Deck, 1 = Deck Builder, 2 = Options
+        // (plus "Resume session" on top when there is one to resume)
         const MENU_ITEMS: usize = 3;
-        let max_index = MENU_ITEMS.saturating_sub(1);
+        let items = MENU_ITEMS + usize::from(self.resumable.is_some());
+        let max_index = items.saturating_sub(1);
 
         ctx.input(|i| {
             // Keyboard up/down
@@ -565,7 +681,7 @@ impl MorflashGui {
     }
 
     fn handle_auto_advance(&mut self) {
-        if !self.pending_advance {
+        if !self.pending_advance || self.awaiting_continue {
             return;
         }
 
@@ -579,6 +695,15 @@ impl MorflashGui {
     }
 }
 impl MorflashGui {
+    /// Show a toast-style notice for a few seconds.
+    pub(crate) fn notify(&mut self, message: String, is_error: bool) {
+        self.save_notice = Some(SaveNotice {
+            message,
+            is_error,
+            created_at: Instant::now(),
+        });
+    }
+
     fn show_save_notice(&mut self, ctx: &egui::Context) {
         use std::time::Duration;
 
@@ -648,7 +773,13 @@ Screen::MainMenu | Screen::DeckList => {
         self.mor_button_tex.as_ref(),
         self.critter_tex.as_ref(),
         &self.options_state.main_menu,
+        self.resumable.as_ref().map(|s| s.deck_key()).as_deref(),
     ) {
+        MainMenuAction::ResumeSession => {
+            self.resume_session();
+            self.main_menu_focus = 0;
+            self.last_main_menu_focus = 0;
+        }
         MainMenuAction::ChooseDeck => {
             let decks_dir = std::path::Path::new("decks");
 
@@ -721,39 +852,236 @@ Screen::MainMenu | Screen::DeckList => {
                     ctx.set_style(old_style);
                 }
 
+                // =========================
+                // STUDY (matching game round)
+                // =========================
+                Screen::Study if self.matching.is_some() => {
+                    let card_fill = match self.options_state.study.card_color_mode {
+                        options_screen::CardColorMode::BuiltIn => Theme::CARD_BG,
+                        options_screen::CardColorMode::Custom => {
+                            self.options_state.study.card_color
+                        }
+                    };
+                    let screen_rect = ctx.screen_rect();
+                    let default_rect = egui::Rect::from_center_size(
+                        screen_rect.center(),
+                        egui::vec2(
+                            (screen_rect.width() * 0.75).clamp(800.0, 1200.0),
+                            (screen_rect.height() * 0.7).clamp(500.0, 900.0),
+                        ),
+                    );
+                    let elapsed = self
+                        .card_shown_at
+                        .map_or(0.0, |t| t.elapsed().as_secs_f32());
+                    // Keep the round timer ticking.
+                    ctx.request_repaint_after(std::time::Duration::from_millis(250));
+
+                    let mut result = matching_screen::MatchingResult::default();
+                    if let Some(round) = self.matching.as_ref() {
+                        egui::Window::new("MatchingCard")
+                            .title_bar(false)
+                            .resizable(true)
+                            .collapsible(false)
+                            .movable(true)
+                            .default_rect(default_rect)
+                            .frame(
+                                egui::Frame::none()
+                                    .fill(card_fill)
+                                    .rounding(egui::Rounding::same(Theme::CARD_ROUNDING))
+                                    .inner_margin(egui::Margin::symmetric(
+                                        Theme::CARD_MARGIN,
+                                        Theme::CARD_MARGIN - 4.0,
+                                    )),
+                            )
+                            .show(ctx, |ui_card| {
+                                result = matching_screen::draw_matching_screen(
+                                    ui_card,
+                                    round,
+                                    elapsed,
+                                    &self.feedback,
+                                    self.reviewed_count,
+                                    self.total_cards,
+                                );
+                            });
+                    }
+
+                    if result.back_to_list {
+                        self.save_progress();
+                        self.save_session();
+                        self.screen = Screen::DeckList;
+                        self.matching = None;
+                        self.feedback.clear();
+                        self.pending_advance = false;
+                        self.last_answer_time = None;
+                        self.celebration_played = false;
+                        self.main_menu_focus = 0;
+                    } else {
+                        self.handle_matching_pick(result.term, result.definition);
+                    }
+                }
+
                 // =========================
                 // STUDY
                 // =========================
                 Screen::Study => {
-                    let current_card = self
-                        .current_card_id
-                        .and_then(|id| self.cards.iter().find(|c| c.id == id));
+                    let current_card = self.current_card.as_ref();
+                    let direction = self
+                        .current_key
+                        .map(|key| Direction::split_key(key).1)
+                        .unwrap_or_default();
+
+                    // A sprint ends when its time is up, whatever card is
+                    // showing.
+                    let now = chrono::Utc::now();
+                    if self.quiz.as_ref().is_some_and(|q| q.is_time_up(now)) {
+                        self.finish_quiz(now);
+                        return;
+                    }
 
-                    // Completion transition
+                    // Completion transition (also taken straight away when
+                    // the deck had nothing due).
                     if current_card.is_none()
-                        && self.total_cards > 0
+                        && self.selected_deck_name.is_some()
                         && !self.celebration_played
                     {
-                        if let Some(ref sm) = self.sound {
-                            if self.options_state.global.sound_enabled {
-                                sm.play("complete");
+                        let nothing_due = self.total_cards == 0;
+                        if !nothing_due {
+                            if let Some(ref sm) = self.sound {
+                                if self.options_state.global.sound_enabled {
+                                    sm.play("complete");
+                                }
                             }
                         }
                         self.celebration_played = true;
+                        self.completion_state.nothing_due = nothing_due;
+                        // Nothing to celebrate if nothing was studied.
+                        self.completion_state.celebration_played = nothing_due;
+                        let studied = self.studied_states();
+                        self.completion_state.next_due = srs::queue::next_due(&studied);
+                        self.completion_state.forecast = srs::forecast::forecast(
+                            &studied,
+                            self.scheduler.as_ref(),
+                            &self.daily_limits,
+                            &self.daily,
+                            chrono::Utc::now(),
+                            self.options_state.study.day_rollover_hour,
+                            FORECAST_DAYS,
+                        );
+                        self.completion_state.seen = self.session.seen_count();
+                        self.completion_state.mastered = self.session.mastered_count();
+                        self.completion_state.matching = (self.options_state.study.study_mode
+                            == options_screen::StudyMode::Matching)
+                            .then_some(self.matching_score);
+                        self.completion_state.leeches = self
+                            .session_leeches
+                            .iter()
+                            .filter_map(|key| {
+                                let (id, _) = Direction::split_key(*key);
+                                self.cards.iter().find(|c| c.id == id)
+                            })
+                            .map(|c| c.term.clone())
+                            .collect();
+                        self.save_progress();
+                        self.clear_session();
                         self.screen = Screen::Completion;
                         return;
                     }
 
                     let correct_term = self.correct_term.as_deref();
                     let wrong_term = self.wrong_term.as_deref();
-                    let progress = if self.total_cards == 0 {
-                        0.0
+                    // Progress bar: answers so far, or (learning until
+                    // correct) cards answered correctly at least once.
+                    // Sprints count down their time instead.
+                    let progress = if let Some(quiz) = self.quiz.as_ref() {
+                        match (quiz.remaining_secs(now), quiz.time_limit_secs) {
+                            (Some(left), Some(limit)) => {
+                                // Keep the countdown ticking.
+                                ctx.request_repaint_after(std::time::Duration::from_millis(250));
+                                Some((
+                                    left / limit as f32,
+                                    format!(
+                                        "⏱ {left:.0} s left · {} correct",
+                                        quiz.correct_count()
+                                    ),
+                                ))
+                            }
+                            _ => (self.total_cards > 0).then(|| {
+                                (
+                                    self.reviewed_count as f32 / self.total_cards as f32,
+                                    format!(
+                                        "Question {}/{}",
+                                        (self.reviewed_count + 1).min(self.total_cards),
+                                        self.total_cards
+                                    ),
+                                )
+                            }),
+                        }
+                    } else if self.options_state.study.learn_until_correct {
+                        let cards = self.session.card_count();
+                        let mastered = self.session.mastered_count();
+                        (cards > 0).then(|| {
+                            (
+                                mastered as f32 / cards as f32,
+                                format!(
+                                    "{mastered}/{cards} mastered · {} seen",
+                                    self.session.seen_count()
+                                ),
+                            )
+                        })
                     } else {
-                        self.reviewed_count as f32 / self.total_cards as f32
+                        (self.total_cards > 0).then(|| {
+                            (
+                                self.reviewed_count as f32 / self.total_cards as f32,
+                                format!("{}/{}", self.reviewed_count, self.total_cards),
+                            )
+                        })
+                    };
+
+                    let mut result = study_screen::StudyResult::default();
+                    let flip = study_screen::FlipCard {
+                        revealed: self.revealed,
+                        intervals: self.grade_preview,
+                    };
+                    let mut card_action: Option<study_screen::CardAction> = None;
+                    let current_flag = self
+                        .current_key
+                        .and_then(|key| self.states.get(&key))
+                        .and_then(|s| s.flag);
+                    let in_quiz = self.quiz.is_some();
+                    // Exams say nothing about a card until they're over.
+                    let show_details = !self.quiz.as_ref().is_some_and(|q| {
+                        q.kind == srs::quiz::QuizKind::Exam
+                    }) && match self.options_state.study.details {
+                        options_screen::DetailsVisibility::Always => true,
+                        options_screen::DetailsVisibility::AfterAnswer => {
+                            self.pending_advance || self.revealed
+                        }
+                        options_screen::DetailsVisibility::Never => false,
                     };
 
-                    let mut clicked_term: Option<String> = None;
-                    let mut back_to_list = false;
+                    // Card media: the image (the current frame of a GIF,
+                    // kept moving), and whether there is audio to replay.
+                    let media = self.current_media();
+                    let image = match media.as_ref() {
+                        Some((MediaKind::Image, path)) => {
+                            self.media.image(ctx, path).map(|image| {
+                                let secs = self
+                                    .card_shown_at
+                                    .map_or(0.0, |t| t.elapsed().as_secs_f32());
+                                let (texture, next_frame) = image.frame_at(secs);
+                                if image.is_animated() {
+                                    ctx.request_repaint_after(
+                                        std::time::Duration::from_secs_f32(next_frame),
+                                    );
+                                }
+                                texture.clone()
+                            })
+                        }
+                        _ => None,
+                    };
+                    let has_audio = matches!(media, Some((MediaKind::Audio, _)));
+                    let mut replay_audio = has_audio
+                        && ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::R));
 
                     let card_fill = match self.options_state.study.card_color_mode {
                         options_screen::CardColorMode::BuiltIn => Theme::CARD_BG,
@@ -794,27 +1122,77 @@ Screen::MainMenu | Screen::DeckList => {
                                 )),
                         )
                         .show(ctx, |ui_card| {
-                            let (ct, back) = study_screen::draw_study_screen(
+                            if current_card.is_some()
+                                && study_screen::draw_card_media(
+                                    ui_card,
+                                    image.as_ref(),
+                                    has_audio,
+                                )
+                            {
+                                replay_audio = true;
+                            }
+
+                            result = study_screen::draw_study_screen(
                                 ui_card,
-                                current_card,
-                                &self.options,
-                                correct_term,
-                                wrong_term,
-                                &self.feedback,
-                                progress,
-                                self.reviewed_count,
-                                self.total_cards,
+                                study_screen::StudyView {
+                                    current_card: current_card.map(|card| (card, direction)),
+                                    options: &self.options,
+                                    correct_term,
+                                    wrong_term,
+                                    feedback: &self.feedback,
+                                    progress,
+                                    typed: study_screen::TypedAnswer {
+                                        text: &mut self.typed_answer,
+                                        diff: &self.typed_diff,
+                                    },
+                                    flip,
+                                    answered: self.pending_advance,
+                                    awaiting_continue: self.awaiting_continue,
+                                },
                                 &self.options_state.study,
                             );
 
-                            clicked_term = ct;
-                            back_to_list = back;
+                            if let Some(card) = current_card.filter(|_| show_details) {
+                                ui_card.add_space(8.0);
+                                study_screen::draw_card_details(ui_card, card);
+                            }
+
+                            // Quizzes leave the cards' review state alone.
+                            if current_card.is_some() && !in_quiz {
+                                ui_card.add_space(8.0);
+                                card_action =
+                                    study_screen::draw_card_actions(ui_card, current_flag);
+                            }
                         });
 
+                    if replay_audio {
+                        self.play_card_audio();
+                    }
+                    if let Some(action) = card_action {
+                        self.apply_card_action(action);
+                    }
+
+                    if result.reveal {
+                        self.revealed = true;
+                        self.grade_preview = self.grade_intervals(chrono::Utc::now());
+                    }
+                    if let Some(rating) = result.self_grade {
+                        self.handle_self_grade(rating);
+                    }
+
+                    if result.advance {
+                        self.pending_advance = false;
+                        self.pick_next_card(chrono::Utc::now());
+                    }
+
                     // Back to deck list
-                    if back_to_list {
+                    if result.back_to_list {
+                        self.save_progress();
+                        self.save_session();
                         self.screen = Screen::DeckList;
-                        self.current_card_id = None;
+                        self.quiz = None;
+                        self.current_key = None;
+                        self.current_card = None;
                         self.feedback.clear();
                         self.last_answer_correct = None;
                         self.correct_term = None;
@@ -826,7 +1204,7 @@ Screen::MainMenu | Screen::DeckList => {
                     }
 
                     // Handle answer click + sound
-                    if let Some(term) = clicked_term {
+                    if let Some(term) = result.answer {
                         if !self.pending_advance {
                             self.handle_answer(&term);
                             if let Some(ref sm) = self.sound {
@@ -861,7 +1239,8 @@ Screen::MainMenu | Screen::DeckList => {
                         self.main_menu_focus = 0;
                         self.celebration_played = false;
                         self.completion_state.celebration_played = false;
-                        self.current_card_id = None;
+                        self.current_key = None;
+                        self.current_card = None;
                         self.feedback.clear();
                         self.last_answer_correct = None;
                         self.correct_term = None;
@@ -871,6 +1250,32 @@ Screen::MainMenu | Screen::DeckList => {
                     }
                 }
 
+                // =========================
+                // QUIZ RESULTS
+                // =========================
+                Screen::QuizResults => {
+                    let action = match self.quiz_report.as_ref() {
+                        Some(report) => quiz_results_screen::draw_quiz_results_screen(ui, report),
+                        None => quiz_results_screen::QuizResultsAction::BackToList,
+                    };
+
+                    match action {
+                        quiz_results_screen::QuizResultsAction::ExportJson => {
+                            self.export_quiz_report(false);
+                        }
+                        quiz_results_screen::QuizResultsAction::ExportCsv => {
+                            self.export_quiz_report(true);
+                        }
+                        quiz_results_screen::QuizResultsAction::BackToList => {
+                            self.screen = Screen::DeckList;
+                            self.main_menu_focus = 0;
+                            self.celebration_played = false;
+                            self.feedback.clear();
+                        }
+                        quiz_results_screen::QuizResultsAction::None => {}
+                    }
+                }
+
                 // =========================
                 // DECK BUILDER
                 // =========================
@@ -884,6 +1289,7 @@ Screen::MainMenu | Screen::DeckList => {
                     if done {
                         // The deck builder screen itself has already decided whether
                         // to save (via "Save & Exit") or just exit without saving.
+                        self.refresh_decks();
                         self.screen = Screen::DeckList;
                     }
                 }
@@ -939,4 +1345,11 @@ impl App for MorflashGui {
         self.handle_auto_advance();
         self.draw_main_ui(ctx);
     }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.save_progress();
+        if self.screen == Screen::Study {
+            self.save_session();
+        }
+    }
 }
@@ -1,8 +1,12 @@
 // src/gui/app/screens/completion_screen.rs
+use chrono::{DateTime, Local, Utc};
 use eframe::egui;
 use std::time::Instant;
 
 use crate::gui::app::screens::options_screen::CompletionOptions;
+use crate::gui::theme::Theme;
+use crate::srs::matching::MatchScore;
+use crate::srs::ForecastDay;
 
 #[derive(Debug, Clone)]
 pub struct CompletionState {
@@ -18,6 +22,21 @@ pub struct CompletionState {
     pub auto_return_enabled: bool,
     pub auto_return_secs: f32,
     pub auto_return_deadline: Option<Instant>,
+
+    /// The session ended immediately because no cards were due.
+    pub nothing_due: bool,
+    /// When the next card in the deck falls due, if known.
+    pub next_due: Option<DateTime<Utc>>,
+    /// Terms of cards that became leeches this session.
+    pub leeches: Vec<String>,
+    /// Projected reviews for the coming days.
+    pub forecast: Vec<ForecastDay>,
+    /// Distinct cards answered this session, and those answered
+    /// correctly at least once.
+    pub seen: usize,
+    pub mastered: usize,
+    /// Totals of a matching-game session.
+    pub matching: Option<MatchScore>,
 }
 
 impl Default for CompletionState {
@@ -33,6 +52,13 @@ impl Default for CompletionState {
             auto_return_enabled: false,
             auto_return_secs: 5.0,
             auto_return_deadline: None,
+            nothing_due: false,
+            next_due: None,
+            leeches: Vec::new(),
+            forecast: Vec::new(),
+            seen: 0,
+            mastered: 0,
+            matching: None,
         }
     }
 }
@@ -86,10 +112,22 @@ where
     ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
         ui.add_space(40.0);
 
-        ui.heading("Session Complete! 🎉");
-        ui.add_space(12.0);
+        if state.nothing_due {
+            ui.heading("Nothing due right now");
+            ui.add_space(12.0);
+
+            ui.label("No cards in this deck are due for review.");
+        } else {
+            ui.heading("Session Complete! 🎉");
+            ui.add_space(12.0);
 
-        ui.label("You've reviewed all due cards for now.");
+            ui.label("You've reviewed all due cards for now.");
+        }
+
+        if let Some(next) = state.next_due {
+            ui.add_space(6.0);
+            ui.label(format!("Next review due: {}", format_due(next)));
+        }
         ui.add_space(24.0);
 
         egui::Frame::group(ui.style())
@@ -109,6 +147,13 @@ where
                         state.correct_count, state.incorrect_count
                     ));
 
+                    if state.seen > 0 {
+                        ui.label(format!(
+                            "Cards seen: {}  |  Mastered: {}",
+                            state.seen, state.mastered
+                        ));
+                    }
+
                     if state.new_count > 0 {
                         ui.label(format!("New cards: {}", state.new_count));
                     }
@@ -133,6 +178,56 @@ where
                 ui.add_space(8.0);
             });
 
+        if let Some(score) = state.matching.filter(|s| s.pairs > 0) {
+            ui.add_space(16.0);
+            egui::Frame::group(ui.style())
+                .rounding(egui::Rounding::same(8.0))
+                .fill(ui.visuals().extreme_bg_color)
+                .show(ui, |ui| {
+                    ui.set_min_width(260.0);
+                    ui.vertical_centered(|ui| {
+                        ui.label(format!("🧩 Matching score: {}", score.points()));
+                        ui.add_space(4.0);
+                        ui.label(format!(
+                            "{} pairs  |  {} mistake(s)  |  {:.0} s",
+                            score.pairs, score.mistakes, score.secs
+                        ));
+                    });
+                });
+        }
+
+        if state.forecast.iter().any(|d| d.total() > 0) {
+            ui.add_space(16.0);
+            egui::Frame::group(ui.style())
+                .rounding(egui::Rounding::same(8.0))
+                .fill(ui.visuals().extreme_bg_color)
+                .show(ui, |ui| {
+                    ui.vertical_centered(|ui| {
+                        ui.label(format!("📅 Next {} days", state.forecast.len()));
+                        ui.add_space(4.0);
+                        draw_forecast_chart(ui, &state.forecast);
+                    });
+                });
+        }
+
+        if !state.leeches.is_empty() {
+            ui.add_space(16.0);
+            egui::Frame::group(ui.style())
+                .rounding(egui::Rounding::same(8.0))
+                .fill(ui.visuals().extreme_bg_color)
+                .show(ui, |ui| {
+                    ui.set_min_width(260.0);
+                    ui.vertical_centered(|ui| {
+                        ui.label(format!("🩸 New leeches ({})", state.leeches.len()));
+                        ui.small("These keep slipping — consider rewording them.");
+                        ui.add_space(4.0);
+                        for term in &state.leeches {
+                            ui.label(term);
+                        }
+                    });
+                });
+        }
+
         ui.add_space(32.0);
 
         let back_button =
@@ -147,3 +242,79 @@ where
 
     go_back
 }
+
+/// Bar chart of the forecast: reviews (bottom) and new cards (top) per day.
+fn draw_forecast_chart(ui: &mut egui::Ui, days: &[ForecastDay]) {
+    const BAR_W: f32 = 18.0;
+    const GAP: f32 = 6.0;
+    const CHART_H: f32 = 80.0;
+    const LABEL_H: f32 = 16.0;
+
+    let width = days.len() as f32 * (BAR_W + GAP);
+    let (rect, response) =
+        ui.allocate_exact_size(egui::vec2(width, CHART_H + LABEL_H), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+
+    let max = days.iter().map(ForecastDay::total).max().unwrap_or(0).max(1) as f32;
+    let mut hovered: Option<&ForecastDay> = None;
+
+    for (i, day) in days.iter().enumerate() {
+        let x = rect.left() + i as f32 * (BAR_W + GAP) + GAP / 2.0;
+        let base = rect.top() + CHART_H;
+        let review_h = day.reviews as f32 / max * CHART_H;
+        let new_h = day.new_cards as f32 / max * CHART_H;
+
+        let review_rect = egui::Rect::from_min_max(
+            egui::pos2(x, base - review_h),
+            egui::pos2(x + BAR_W, base),
+        );
+        let new_rect = egui::Rect::from_min_max(
+            egui::pos2(x, base - review_h - new_h),
+            egui::pos2(x + BAR_W, base - review_h),
+        );
+        painter.rect_filled(review_rect, 2.0, Theme::NEON_CYAN);
+        painter.rect_filled(new_rect, 2.0, Theme::NEON_PURPLE);
+
+        painter.text(
+            egui::pos2(x + BAR_W / 2.0, base + 2.0),
+            egui::Align2::CENTER_TOP,
+            day.date.format("%d").to_string(),
+            egui::FontId::proportional(10.0),
+            ui.visuals().weak_text_color(),
+        );
+
+        let column = egui::Rect::from_min_max(
+            egui::pos2(x, rect.top()),
+            egui::pos2(x + BAR_W, base),
+        );
+        if response
+            .hover_pos()
+            .is_some_and(|p| column.expand2(egui::vec2(GAP / 2.0, 0.0)).contains(p))
+        {
+            hovered = Some(day);
+        }
+    }
+
+    if let Some(day) = hovered {
+        response.on_hover_text(format!(
+            "{}: {} reviews, {} new",
+            day.date.format("%a %d %b"),
+            day.reviews,
+            day.new_cards
+        ));
+    }
+}
+
+/// "today 14:05", "tomorrow 09:00" or "Mon 24 Nov 09:00" in local time.
+fn format_due(when: DateTime<Utc>) -> String {
+    let local = when.with_timezone(&Local);
+    let today = Local::now().date_naive();
+
+    if local.date_naive() <= today {
+        format!("today {}", local.format("%H:%M"))
+    } else if today.succ_opt() == Some(local.date_naive()) {
+        format!("tomorrow {}", local.format("%H:%M"))
+    } else {
+        local.format("%a %d %b %H:%M").to_string()
+    }
+}
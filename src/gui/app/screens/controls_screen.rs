@@ -54,6 +54,7 @@ pub fn draw_controls_screen(ui: &mut egui::Ui, ctx: &egui::Context) -> bool {
             ui.add_space(4.0);
             ui.label("• Click correct meaning — Answer the card");
             ui.label("• Esc — Return to deck list");
+            ui.label("• Ctrl + R — Replay the card's audio");
             ui.label("• Space / Enter (optional) — Submit");
             ui.label("• ← / → (optional) — Next/Previous card");
             ui.add_space(12.0);
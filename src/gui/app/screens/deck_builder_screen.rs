@@ -1,17 +1,27 @@
 use eframe::egui;
 use rfd::FileDialog;
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
+use crate::gui::app::deck_ops::builder_ops;
 use crate::gui::app::screens::options_screen::DeckBuilderOptions;
+use crate::gui::app::screens::study_screen::flag_picker;
 use crate::gui::theme::MenuTheme;
 use crate::import;
-use crate::model::Deck;
+use crate::model::{Deck, Direction, ReviewState};
+use crate::srs::{cloze, leech::LEECH_TAG, mflash, progress};
 
 /// One flashcard being edited in the deck builder.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct BuilderCard {
+    /// Id of the card in the deck (`0` until one is assigned, see
+    /// `assign_card_ids`); study status is kept by it.
+    #[serde(default)]
+    pub id: u64,
+
     pub term: String,
     pub definition: String,
 
@@ -31,6 +41,21 @@ pub struct BuilderCard {
 
     /// Example sentences for this card.
     pub examples: Vec<String>,
+
+    /// Free-form notes shown with the card's details.
+    #[serde(default)]
+    pub notes: String,
+}
+
+impl BuilderCard {
+    /// How many cards the deck gets from this one: one per deletion of a
+    /// cloze note, else one.
+    fn card_count(&self) -> u64 {
+        [&self.definition, &self.term]
+            .into_iter()
+            .find(|text| cloze::is_cloze(text))
+            .map_or(1, |note| cloze::indices(note).len() as u64)
+    }
 }
 
 /// State for the deck builder screen.
@@ -47,6 +72,15 @@ pub struct DeckBuilderState {
 
     /// All cards in this deck.
     pub cards: Vec<BuilderCard>,
+
+    /// Saved review states for the deck being edited, so cards can be
+    /// suspended or flagged from here. Keyed by card id (`BuilderCard::id`).
+    #[serde(skip)]
+    pub progress: HashMap<u64, ReviewState>,
+
+    /// Deck key (file stem) that `progress` was loaded for.
+    #[serde(skip)]
+    pub progress_key: Option<String>,
 }
 
 /// Draw the deck builder screen.
@@ -194,15 +228,55 @@ egui::CentralPanel::default().show(ctx, |ui| {
     ui.add_space(8.0);
 
     let mut remove_index: Option<usize> = None;
+    let mut status_changed: Vec<usize> = Vec::new();
+    assign_card_ids(&mut state.cards);
+
+    // Follow the deck's progress file as the file name changes.
+    let deck_key = safe_deck_name(&state.file_name);
+    if state.progress_key.as_deref() != Some(deck_key.as_str()) {
+        let path = progress::progress_path(Path::new(progress::PROGRESS_DIR), &deck_key);
+        state.progress = progress::load_progress(&path).unwrap_or_default();
+        state.progress_key = Some(deck_key.clone());
+    }
 
     egui::ScrollArea::vertical()
         .auto_shrink([false, false])
         .show(ui, |ui| {
             for (idx, card) in state.cards.iter_mut().enumerate() {
                 ui.group(|ui| {
-                    // Header row: "Card X" and Remove on the right.
+                    // Header row: "Card X", study status, and Remove on the right.
                     ui.horizontal(|ui| {
                         ui.label(format!("Card {}", idx + 1));
+                        ui.add_space(12.0);
+
+                        // Edit a copy: a card only gets a progress entry once
+                        // its status is actually changed.
+                        let id = card.id;
+                        let now = Utc::now();
+                        let saved = state.progress.get(&id);
+                        let mut flag = saved.and_then(|s| s.flag);
+                        let mut suspended = saved.is_some_and(|s| s.suspended);
+                        let buried = saved.is_some_and(|s| s.is_buried(now));
+                        let leech = saved.is_some_and(|s| s.has_tag(LEECH_TAG));
+
+                        let mut changed = flag_picker(ui, (idx, "flag"), &mut flag);
+                        changed |= ui.checkbox(&mut suspended, "Suspended").changed();
+                        let unbury = buried && ui.button("Unbury").clicked();
+                        if changed || unbury {
+                            let status = state
+                                .progress
+                                .entry(id)
+                                .or_insert_with(|| ReviewState::new(id, now));
+                            status.flag = flag;
+                            status.suspended = suspended;
+                            if unbury {
+                                status.buried_until = None;
+                            }
+                            status_changed.push(idx);
+                        }
+                        if leech {
+                            ui.label(egui::RichText::new("leech").color(egui::Color32::LIGHT_RED));
+                        }
 
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                             if ui.button("🗑 Remove").clicked() {
@@ -308,6 +382,12 @@ egui::CentralPanel::default().show(ctx, |ui| {
                     }
 
                     ui.add_space(6.0);
+
+                    // Notes – shown with the card's details while studying.
+                    ui.label("Notes (optional):");
+                    ui.text_edit_multiline(&mut card.notes);
+
+                    ui.add_space(6.0);
                 });
 
                 ui.add_space(10.0);
@@ -323,6 +403,11 @@ egui::CentralPanel::default().show(ctx, |ui| {
             });
         });
 
+    if !status_changed.is_empty() {
+        let changed = deck_card_ids(state, &status_changed);
+        save_card_status(&deck_key, &mut state.progress, &changed);
+    }
+
     // Actually remove card after iterating.
     if let Some(i) = remove_index {
         if i < state.cards.len() {
@@ -355,17 +440,32 @@ fn import_deck_into_builder(path: &Path, state: &mut DeckBuilderState) -> Result
         }
     }
 
-    // APPEND imported cards instead of clearing existing ones.
+    // APPEND imported cards instead of clearing existing ones. Their ids
+    // (and so their study status) only carry over into an empty builder.
+    let keep_ids = state.cards.is_empty();
     for src in deck.cards {
+        // A cloze note is one card per deletion; keep the note once.
+        if let Some(index) = src.cloze {
+            if cloze::indices(&src.definition).first() != Some(&index) {
+                continue;
+            }
+        }
+
         let mut card = BuilderCard::default();
-        card.term = src.term;
+        card.id = if keep_ids { src.id } else { 0 };
+        card.term = if src.cloze.is_some() {
+            cloze::reveal(&src.definition)
+        } else {
+            src.term
+        };
         card.definition = src.definition;
-
-        // TODO: when Deck/Card support languages/tags/examples/media/notes,
-        // copy them across here as needed.
-        // card.tags = src.tags.clone();
-        // card.examples = src.examples.clone();
-        // card.media_path = src.media.unwrap_or_default();
+        card.media_path = src.media.unwrap_or_default();
+        card.hyperlink = src.hyperlink.unwrap_or_default();
+        card.examples = src.examples;
+        card.notes = src.notes.unwrap_or_default();
+        card.term_lang = src.term_lang.unwrap_or_default();
+        card.def_lang = src.def_lang.unwrap_or_default();
+        card.tags = src.tags;
 
         state.cards.push(card);
     }
@@ -471,19 +571,97 @@ fn card_media_widget(ui: &mut egui::Ui, media_path: &mut String) {
     }
 }
 
-/// Save the current deck to `decks/<safe_name>.json`.
-/// Returns `true` on success.
-fn save_deck_to_disk(state: &DeckBuilderState) -> bool {
-    let decks_dir = Path::new("decks");
-    if let Err(e) = fs::create_dir_all(decks_dir) {
-        eprintln!("MorFlash: failed to create decks dir {:?}: {e}", decks_dir);
-        return false;
+/// Give the cards the ids the saved deck will have (see
+/// `mflash::card_ids`): ids already given are kept, and cards added in
+/// the builder (or loaded without ids) get new ones above them.
+fn assign_card_ids(cards: &mut [BuilderCard]) {
+    let notes = cards
+        .iter()
+        .map(|c| ((c.id != 0).then_some(c.id), c.card_count()));
+    let ids = mflash::card_ids(notes);
+    for (card, ids) in cards.iter_mut().zip(ids) {
+        card.id = ids[0];
+    }
+}
+
+/// For each of the builder cards at `indices`, its id and the ids of the
+/// cards it becomes in the saved deck (one per deletion of a cloze note),
+/// taken from the same conversion as saving.
+fn deck_card_ids(state: &DeckBuilderState, indices: &[usize]) -> Vec<(u64, Vec<u64>)> {
+    let deck = Deck::from(builder_ops::builder_deck(state));
+    let mut deck_cards = deck.cards.iter();
+    let ids: Vec<Vec<u64>> = state
+        .cards
+        .iter()
+        .map(|card| {
+            deck_cards
+                .by_ref()
+                .take(card.card_count() as usize)
+                .map(|c| c.id)
+                .collect()
+        })
+        .collect();
+
+    indices
+        .iter()
+        .map(|&i| (state.cards[i].id, ids[i].clone()))
+        .collect()
+}
+
+/// Write the status (suspended / buried / flag) of the cards in `changed`
+/// (builder card id, ids of its cards in the deck) to the deck's progress
+/// file: a cloze note's status goes to each of its cards, and a card's
+/// forward direction, if it has been studied, gets the same status. The
+/// file is re-read first so reviews done since the builder loaded it
+/// aren't overwritten.
+fn save_card_status(
+    deck_key: &str,
+    status: &mut HashMap<u64, ReviewState>,
+    changed: &[(u64, Vec<u64>)],
+) {
+    let path = progress::progress_path(Path::new(progress::PROGRESS_DIR), deck_key);
+    let mut states = match progress::load_progress(&path) {
+        Ok(states) => states,
+        Err(e) => {
+            eprintln!("MorFlash: not saving card status: {e:#}");
+            return;
+        }
+    };
+
+    for (builder_id, ids) in changed {
+        let Some(changed) = status.get(builder_id) else {
+            continue;
+        };
+        for &id in ids {
+            let saved = states
+                .entry(id)
+                .or_insert_with(|| ReviewState { card_id: id, ..changed.clone() });
+            saved.suspended = changed.suspended;
+            saved.buried_until = changed.buried_until;
+            saved.flag = changed.flag;
+
+            if let Some(forward) = states.get_mut(&Direction::Forward.key(id)) {
+                forward.suspended = changed.suspended;
+                forward.buried_until = changed.buried_until;
+                forward.flag = changed.flag;
+            }
+        }
+    }
+
+    if let Err(e) = progress::save_progress(&path, &states) {
+        eprintln!("MorFlash: failed to save card status: {e:#}");
+        return;
     }
+    *status = states;
+}
 
-    let raw_name = state.file_name.trim();
+/// File stem the deck is saved under: the title with anything other than
+/// ASCII letters, digits, `-` and `_` replaced by `_`.
+pub(crate) fn safe_deck_name(file_name: &str) -> String {
+    let raw_name = file_name.trim();
     let base_name = if raw_name.is_empty() { "new_deck" } else { raw_name };
 
-    let safe_name: String = base_name
+    base_name
         .chars()
         .map(|c| {
             if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
@@ -492,22 +670,19 @@ fn save_deck_to_disk(state: &DeckBuilderState) -> bool {
                 '_'
             }
         })
-        .collect();
-
-    let path = decks_dir.join(format!("{safe_name}.json"));
+        .collect()
+}
 
-    match serde_json::to_string_pretty(state) {
-        Ok(json) => {
-            if let Err(e) = fs::write(&path, json) {
-                eprintln!("MorFlash: failed to save deck to {:?}: {e}", path);
-                false
-            } else {
-                println!("MorFlash: deck saved to {:?}", path);
-                true
-            }
+/// Save the current deck to `decks/<safe_name>.json` (see
+/// `builder_ops::save_builder_deck`). Returns `true` on success.
+fn save_deck_to_disk(state: &DeckBuilderState) -> bool {
+    match builder_ops::save_builder_deck(state, Path::new("decks")) {
+        Ok(path) => {
+            println!("MorFlash: deck saved to {:?}", path);
+            true
         }
         Err(e) => {
-            eprintln!("MorFlash: failed to serialize deck: {e}");
+            eprintln!("MorFlash: failed to save deck: {e:#}");
             false
         }
     }
@@ -531,3 +706,104 @@ fn import_from_json(path: &Path, state: &mut DeckBuilderState) -> Result<(), Str
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn card_notes_survive_saving_and_reopening() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = DeckBuilderState {
+            file_name: "Pets".to_string(),
+            cards: vec![
+                BuilderCard {
+                    term: "dog".to_string(),
+                    definition: "a barking pet".to_string(),
+                    notes: "Not a wolf.".to_string(),
+                    ..Default::default()
+                },
+                BuilderCard {
+                    term: "cat".to_string(),
+                    definition: "a purring pet".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let path = builder_ops::save_builder_deck(&state, dir.path()).unwrap();
+        assert_eq!(path, dir.path().join("Pets.json"));
+
+        // What the study screen opens...
+        let deck = Deck::from_json_file(&path).unwrap();
+        assert_eq!(deck.cards[0].notes.as_deref(), Some("Not a wolf."));
+        assert_eq!(deck.cards[1].notes, None);
+
+        // ...and what the builder edits again.
+        let mut reopened = DeckBuilderState::default();
+        import_deck_into_builder(&path, &mut reopened).unwrap();
+        let notes: Vec<&str> = reopened.cards.iter().map(|c| c.notes.as_str()).collect();
+        assert_eq!(notes, ["Not a wolf.", ""]);
+    }
+
+    #[test]
+    fn card_tags_and_language_survive_saving_and_reopening() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = DeckBuilderState {
+            file_name: "French".to_string(),
+            cards: vec![BuilderCard {
+                term: "chat".to_string(),
+                definition: "cat".to_string(),
+                term_lang: "fr".to_string(),
+                tags: vec!["animals".to_string(), LEECH_TAG.to_string()],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let path = builder_ops::save_builder_deck(&state, dir.path()).unwrap();
+        let mut reopened = DeckBuilderState::default();
+        import_deck_into_builder(&path, &mut reopened).unwrap();
+        let path = builder_ops::save_builder_deck(&reopened, dir.path()).unwrap();
+
+        let deck = Deck::from_json_file(&path).unwrap();
+        assert_eq!(deck.cards[0].term_lang.as_deref(), Some("fr"));
+        assert_eq!(deck.cards[0].tags, ["animals", LEECH_TAG]);
+    }
+
+    #[test]
+    fn cloze_note_status_goes_to_its_own_cards_only() {
+        let card = |id, term: &str| BuilderCard {
+            id,
+            term: term.to_string(),
+            definition: "...".to_string(),
+            ..Default::default()
+        };
+        // As imported from a hand-written deck: the note's second card
+        // would clash with card 2.
+        let mut state = DeckBuilderState {
+            cards: vec![
+                card(1, "{{c1::Paris}} is in {{c2::France}}"),
+                card(2, "dog"),
+                card(0, "cat"),
+            ],
+            ..Default::default()
+        };
+        assign_card_ids(&mut state.cards);
+        let ids: Vec<u64> = state.cards.iter().map(|c| c.id).collect();
+        assert_eq!(ids, [1, 2, 3]);
+
+        // The builder numbers its cards every frame; nothing moves.
+        assign_card_ids(&mut state.cards);
+        let deck = Deck::from(builder_ops::builder_deck(&state));
+        let deck_ids: Vec<u64> = deck.cards.iter().map(|c| c.id).collect();
+        let displaced = cloze::displaced_id(1, 1).unwrap();
+        assert_eq!(deck_ids, [1, displaced, 2, 3]);
+
+        assert_eq!(
+            deck_card_ids(&state, &[0, 1, 2]),
+            [(1, vec![1, displaced]), (2, vec![2]), (3, vec![3])]
+        );
+    }
+}
@@ -7,6 +7,7 @@ use crate::gui::theme::MenuTheme;
 #[derive(Debug)]
 pub enum MainMenuAction {
     None,
+    ResumeSession,
     ChooseDeck,
     OpenDeckBuilder,
     OpenControls,
@@ -19,6 +20,7 @@ pub fn draw_main_menu(
     mor_button_tex: Option<&TextureHandle>,
     critter_tex: Option<&TextureHandle>,
     main_menu_opts: &MainMenuOptions,
+    resume_deck: Option<&str>,
 ) -> MainMenuAction {
     MenuTheme::apply_to_ctx(ui.ctx());
 
@@ -28,11 +30,13 @@ pub fn draw_main_menu(
     let mut action = MainMenuAction::None;
     let mut critter_target: Option<egui::Rect> = None;
 
-    // Fixed focus indices
-    let choose_deck_index = 0;
-    let deck_builder_index = 1;
-    let options_index = 2;
-    let controls_index = 3;
+    // Fixed focus indices ("Resume session" takes 0 when it's shown)
+    let offset = usize::from(resume_deck.is_some());
+    let resume_index = 0;
+    let choose_deck_index = offset;
+    let deck_builder_index = 1 + offset;
+    let options_index = 2 + offset;
+    let controls_index = 3 + offset;
 
     ui.vertical_centered(|ui| {
         ui.add_space(32.0);
@@ -46,6 +50,28 @@ pub fn draw_main_menu(
 
         ui.add_space(32.0);
 
+        // --- Resume Session button (only with a saved session) ---
+        if let Some(deck) = resume_deck {
+            let (resume_response, resume_rect) = draw_menu_button(
+                ui,
+                &format!("▶ Resume session ({deck})"),
+                mor_button_tex,
+                260.0,
+            );
+
+            let resume_active = resume_response.hovered() || focus_index == resume_index;
+
+            if resume_active {
+                critter_target = Some(resume_rect);
+            }
+
+            if resume_response.clicked() {
+                action = MainMenuAction::ResumeSession;
+            }
+
+            ui.add_space(18.0);
+        }
+
         // --- Choose Deck button ---
         let (choose_response, choose_rect) =
             draw_menu_button(ui, "Choose a deck...", mor_button_tex, 260.0);
@@ -0,0 +1,208 @@
+// src/gui/app/screens/matching_screen.rs
+
+use eframe::egui;
+
+use crate::gui::theme::Theme;
+use crate::model::Card;
+use crate::srs::cloze;
+use crate::srs::matching::{MatchingRound, MAX_PAIRS};
+
+/// What the learner did on the matching screen this frame.
+#[derive(Debug, Default)]
+pub struct MatchingResult {
+    /// Card whose term was picked.
+    pub term: Option<usize>,
+    /// Card whose definition was picked.
+    pub definition: Option<usize>,
+    pub back_to_list: bool,
+}
+
+const TERM_KEYS: [egui::Key; MAX_PAIRS] = [
+    egui::Key::Num1,
+    egui::Key::Num2,
+    egui::Key::Num3,
+    egui::Key::Num4,
+    egui::Key::Num5,
+    egui::Key::Num6,
+    egui::Key::Num7,
+    egui::Key::Num8,
+];
+
+const DEFINITION_KEYS: [(egui::Key, char); MAX_PAIRS] = [
+    (egui::Key::A, 'A'),
+    (egui::Key::B, 'B'),
+    (egui::Key::C, 'C'),
+    (egui::Key::D, 'D'),
+    (egui::Key::E, 'E'),
+    (egui::Key::F, 'F'),
+    (egui::Key::G, 'G'),
+    (egui::Key::H, 'H'),
+];
+
+/// Draw a matching round: terms on the left (keys 1–8), definitions on
+/// the right (keys A–H), with the round's time and mistakes above.
+pub fn draw_matching_screen(
+    ui: &mut egui::Ui,
+    round: &MatchingRound,
+    elapsed_secs: f32,
+    feedback: &str,
+    reviewed: usize,
+    total: usize,
+) -> MatchingResult {
+    let mut result = MatchingResult::default();
+
+    if !round.is_finished() {
+        ui.ctx().input(|i| {
+            result.term = TERM_KEYS
+                .iter()
+                .zip(&round.terms)
+                .find(|(key, _)| i.key_pressed(**key))
+                .map(|(_, &card)| card);
+            result.definition = DEFINITION_KEYS
+                .iter()
+                .zip(&round.definitions)
+                .find(|((key, _), _)| i.key_pressed(*key))
+                .map(|(_, &card)| card);
+        });
+    }
+
+    let card_width = Theme::card_width(ui.available_width());
+    ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
+        ui.set_width(card_width);
+
+        ui.label(
+            egui::RichText::new("Match the pairs")
+                .size(26.0)
+                .color(Theme::CARD_TEXT),
+        );
+        ui.label(
+            egui::RichText::new(format!(
+                "⏱ {elapsed_secs:.0} s   ✗ {} mistakes",
+                round.total_mistakes()
+            ))
+            .size(18.0)
+            .color(Theme::CARD_TEXT),
+        );
+        ui.add_space(20.0);
+
+        let button_width = card_width / 2.0 - 24.0;
+        ui.columns(2, |columns| {
+            for (pos, &card) in round.terms.iter().enumerate() {
+                let text = format!("{}  {}", pos + 1, round.cards[card].term);
+                let state = PickState::of(
+                    round.is_term_matched(card),
+                    round.selected_term == Some(card),
+                    round.last_mismatch.is_some_and(|(t, _)| t == card),
+                );
+                if pick_button(&mut columns[0], text, state, button_width) {
+                    result.term = Some(card);
+                }
+            }
+            for (pos, &card) in round.definitions.iter().enumerate() {
+                let text = format!(
+                    "{}  {}",
+                    DEFINITION_KEYS[pos].1,
+                    definition_text(&round.cards[card])
+                );
+                let state = PickState::of(
+                    round.is_definition_matched(card),
+                    round.selected_definition == Some(card),
+                    round.last_mismatch.is_some_and(|(_, d)| d == card),
+                );
+                if pick_button(&mut columns[1], text, state, button_width) {
+                    result.definition = Some(card);
+                }
+            }
+        });
+
+        ui.add_space(20.0);
+        if !feedback.is_empty() {
+            ui.label(
+                egui::RichText::new(feedback)
+                    .size(20.0)
+                    .color(Theme::CARD_TEXT),
+            );
+        }
+
+        ui.add_space(12.0);
+        if total > 0 {
+            let bar = egui::ProgressBar::new(reviewed as f32 / total as f32)
+                .desired_width(card_width - 40.0)
+                .text(format!("{reviewed}/{total}"));
+            ui.add(bar);
+        }
+
+        ui.add_space(16.0);
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            let label = egui::RichText::new("← Back to deck list")
+                .size(18.0)
+                .color(Theme::BUTTON_TEXT);
+            let back_button = egui::Button::new(label)
+                .min_size(egui::vec2(200.0, 40.0))
+                .fill(Theme::BUTTON_FILL)
+                .stroke(egui::Stroke::new(2.0, Theme::BUTTON_OUTLINE))
+                .rounding(egui::Rounding::same(10.0));
+            if ui.add(back_button).clicked() {
+                result.back_to_list = true;
+            }
+        });
+    });
+
+    result
+}
+
+/// How a term or definition button is drawn.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PickState {
+    Open,
+    Selected,
+    Mismatched,
+    Matched,
+}
+
+impl PickState {
+    fn of(matched: bool, selected: bool, mismatched: bool) -> Self {
+        if matched {
+            PickState::Matched
+        } else if mismatched {
+            PickState::Mismatched
+        } else if selected {
+            PickState::Selected
+        } else {
+            PickState::Open
+        }
+    }
+}
+
+/// One term / definition button. Returns `true` if clicked.
+fn pick_button(ui: &mut egui::Ui, text: String, state: PickState, width: f32) -> bool {
+    let outline = match state {
+        PickState::Open => Theme::BUTTON_OUTLINE,
+        PickState::Selected => Theme::NEON_PURPLE,
+        PickState::Mismatched => Theme::WRONG_OUTLINE,
+        PickState::Matched => Theme::CORRECT_OUTLINE,
+    };
+    let button = egui::Button::new(
+        egui::RichText::new(text)
+            .size(20.0)
+            .color(Theme::BUTTON_TEXT),
+    )
+    .min_size(egui::vec2(width, 48.0))
+    .fill(Theme::BUTTON_FILL)
+    .stroke(egui::Stroke::new(2.0, outline))
+    .rounding(egui::Rounding::same(12.0));
+
+    let clicked = ui
+        .add_enabled(state != PickState::Matched, button)
+        .clicked();
+    ui.add_space(8.0);
+    clicked
+}
+
+/// Definition column text: cloze notes show their blanked sentence.
+fn definition_text(card: &Card) -> String {
+    match card.cloze {
+        Some(index) => cloze::prompt(&card.definition, index),
+        None => card.definition.clone(),
+    }
+}
@@ -3,6 +3,8 @@
 pub mod completion_screen;
 pub mod deck_builder_screen;
 pub mod main_menu_screen;
+pub mod matching_screen;
 pub mod options_screen;
+pub mod quiz_results_screen;
 pub mod study_screen;
 pub mod controls_screen;
@@ -19,7 +19,9 @@ pub use completion_options::CompletionOptions;
 pub use deck_builder_options::DeckBuilderOptions;
 pub use global_options::GlobalOptions;
 pub use main_menu_options::MainMenuOptions;
-pub use study_options::StudyOptions;
+pub use study_options::{
+    DetailsVisibility, SessionKind, StudyDirections, StudyMode, StudyOptions,
+};
 
 use crate::gui::theme::MenuTheme;
 
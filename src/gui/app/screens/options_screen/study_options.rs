@@ -3,6 +3,147 @@
 use eframe::egui;
 
 use super::state::CardColorMode;
+use crate::model::Direction;
+use crate::srs::quiz::QuizKind;
+use crate::srs::steps::{format_steps, parse_steps};
+use crate::srs::{
+    choice, day, fsrs, matching, AutoGrade, DailyLimits, DistractorStrategy, LearningSteps,
+    LeechSettings, SchedulerKind, SchedulerParams, TypedGrading,
+};
+
+/// Which way cards are asked.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum StudyDirections {
+    /// Term → definition.
+    Forward,
+    /// Definition → term.
+    #[default]
+    Reverse,
+    /// Both, each direction scheduled separately.
+    Both,
+}
+
+impl StudyDirections {
+    pub const ALL: [StudyDirections; 3] = [
+        StudyDirections::Forward,
+        StudyDirections::Reverse,
+        StudyDirections::Both,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StudyDirections::Forward => "Term → definition",
+            StudyDirections::Reverse => "Definition → term",
+            StudyDirections::Both => "Both directions",
+        }
+    }
+
+    pub fn directions(self) -> &'static [Direction] {
+        match self {
+            StudyDirections::Forward => &[Direction::Forward],
+            StudyDirections::Reverse => &[Direction::Reverse],
+            StudyDirections::Both => &Direction::ALL,
+        }
+    }
+}
+
+/// How the learner answers a card.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum StudyMode {
+    /// Pick the term from a grid of buttons.
+    #[default]
+    MultipleChoice,
+    /// Type the term.
+    Typed,
+    /// Reveal the term, then grade yourself Again / Hard / Good / Easy.
+    Flip,
+    /// Timed rounds pairing terms with definitions.
+    Matching,
+}
+
+impl StudyMode {
+    pub const ALL: [StudyMode; 4] = [
+        StudyMode::MultipleChoice,
+        StudyMode::Typed,
+        StudyMode::Flip,
+        StudyMode::Matching,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StudyMode::MultipleChoice => "Multiple choice",
+            StudyMode::Typed => "Type the answer",
+            StudyMode::Flip => "Flip card (self-graded)",
+            StudyMode::Matching => "Matching game",
+        }
+    }
+}
+
+/// When a card's examples, notes and link are shown.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum DetailsVisibility {
+    /// With the question.
+    Always,
+    /// Once the card is answered (or turned over); the card then waits
+    /// for the learner to continue.
+    #[default]
+    AfterAnswer,
+    Never,
+}
+
+impl DetailsVisibility {
+    pub const ALL: [DetailsVisibility; 3] = [
+        DetailsVisibility::Always,
+        DetailsVisibility::AfterAnswer,
+        DetailsVisibility::Never,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DetailsVisibility::Always => "Always",
+            DetailsVisibility::AfterAnswer => "After answering",
+            DetailsVisibility::Never => "Never",
+        }
+    }
+}
+
+/// What a study session is for.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SessionKind {
+    /// Due cards, rescheduled by the answers.
+    #[default]
+    Review,
+    /// As many cards as possible against the clock; progress untouched.
+    Sprint,
+    /// A fixed number of random cards, marked at the end; progress
+    /// untouched.
+    Exam,
+}
+
+impl SessionKind {
+    pub const ALL: [SessionKind; 3] = [
+        SessionKind::Review,
+        SessionKind::Sprint,
+        SessionKind::Exam,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SessionKind::Review => "Spaced review",
+            SessionKind::Sprint => "Sprint (timed)",
+            SessionKind::Exam => "Exam",
+        }
+    }
+
+    /// The quiz this kind of session runs, if it isn't a review.
+    pub fn quiz(self) -> Option<QuizKind> {
+        match self {
+            SessionKind::Review => None,
+            SessionKind::Sprint => Some(QuizKind::Sprint),
+            SessionKind::Exam => Some(QuizKind::Exam),
+        }
+    }
+}
 
 /// Options that control how the study card looks and behaves during review.
 #[derive(Clone, Debug)]
@@ -15,9 +156,44 @@ pub struct StudyOptions {
     /// The custom background color shown when card_color_mode = Custom.
     pub card_color: egui::Color32,
 
-    /// Whether to show the term first (term → definition) or the opposite
-    /// (definition → term).
-    pub show_term_first: bool,
+    /// Which way cards are asked: term → definition, the opposite, or
+    /// both (each direction has its own review state).
+    pub directions: StudyDirections,
+
+    /// When studying both directions, keep the two directions of a card
+    /// out of the same session.
+    pub separate_siblings: bool,
+
+    /// How answers are given: buttons or typing.
+    pub study_mode: StudyMode,
+
+    /// Number of multiple-choice answer buttons (2–8).
+    pub answer_count: usize,
+
+    /// How the wrong answer choices are picked.
+    pub distractor_strategy: DistractorStrategy,
+
+    /// How closely a typed answer has to match, and how many typos
+    /// `TypedGrading::Tolerant` forgives.
+    pub typed_grading: TypedGrading,
+    pub max_typos: usize,
+
+    /// Pairs per matching-game round (3–8).
+    pub matching_pairs: usize,
+
+    /// Review, sprint or exam; how long a sprint lasts (seconds) and how
+    /// many questions an exam has.
+    pub session_kind: SessionKind,
+    pub sprint_secs: u32,
+    pub exam_questions: usize,
+
+    /// Bring missed cards back later in the same session until they are
+    /// answered correctly, `requeue_gap` cards after the miss.
+    pub learn_until_correct: bool,
+    pub requeue_gap: usize,
+
+    /// When the card's examples, notes and hyperlink are shown.
+    pub details: DetailsVisibility,
 
     /// Scales the font size on the study card (1.0 = normal).
     pub font_scale: f32,
@@ -38,6 +214,47 @@ pub struct StudyOptions {
     pub use_custom_progress_colors: bool,
     pub progress_fg_color: egui::Color32,
     pub progress_bg_color: egui::Color32,
+
+    /// Scheduling algorithm used for decks that don't pick their own
+    /// (a deck can override this with `"srs": { "scheduler": ... }`).
+    pub scheduler: SchedulerKind,
+
+    /// Daily caps on new cards and reviews (decks can override these).
+    pub daily_limits: DailyLimits,
+
+    /// Local hour at which a new study day begins (0–23).
+    pub day_rollover_hour: u32,
+
+    /// When repeatedly forgotten cards are flagged as leeches, and whether
+    /// they get suspended.
+    pub leech: LeechSettings,
+
+    /// How multiple-choice answers are turned into Again/Hard/Good/Easy:
+    /// wrong = Again, then graded by response time.
+    pub auto_grade: AutoGrade,
+
+    /// Target probability of recall when a card comes due (decks can
+    /// override this).
+    pub desired_retention: f64,
+
+    /// Parameters fitted from the review log (see `srs::optimizer`) in
+    /// place of the defaults; decks can set their own.
+    pub scheduler_params: SchedulerParams,
+
+    /// Spread intervals by a few percent so cards learned together don't
+    /// all come due on the same day.
+    pub interval_fuzz: bool,
+
+    /// Seed for the fuzz; `None` picks a fresh one each time a deck opens.
+    pub fuzz_seed: Option<u64>,
+
+    /// Learning / relearning steps used for decks that don't set their own.
+    pub learning_steps: LearningSteps,
+
+    /// Text being edited for the step lists (e.g. "1m 10m"); only copied
+    /// into `learning_steps` once it parses.
+    pub learning_steps_text: String,
+    pub relearning_steps_text: String,
 }
 
 impl Default for StudyOptions {
@@ -48,7 +265,20 @@ impl Default for StudyOptions {
             // A dark bluish background very close to your original Theme::CARD_BG.
             card_color: egui::Color32::from_rgb(24, 30, 60),
 
-            show_term_first: true,
+            directions: StudyDirections::default(),
+            separate_siblings: true,
+            study_mode: StudyMode::default(),
+            answer_count: 4,
+            distractor_strategy: DistractorStrategy::default(),
+            typed_grading: TypedGrading::default(),
+            max_typos: 2,
+            matching_pairs: 6,
+            session_kind: SessionKind::default(),
+            sprint_secs: 60,
+            exam_questions: 20,
+            learn_until_correct: false,
+            requeue_gap: 3,
+            details: DetailsVisibility::default(),
             font_scale: 1.0,
             center_card: true,
 
@@ -64,6 +294,33 @@ impl Default for StudyOptions {
             // Foreground = teal-ish; background = dark muted.
             progress_fg_color: egui::Color32::from_rgb(80, 210, 180),
             progress_bg_color: egui::Color32::from_rgb(30, 40, 60),
+
+            scheduler: SchedulerKind::Sm2,
+            daily_limits: DailyLimits::default(),
+            day_rollover_hour: day::DEFAULT_ROLLOVER_HOUR,
+            leech: LeechSettings::default(),
+            auto_grade: AutoGrade::default(),
+
+            desired_retention: fsrs::DEFAULT_DESIRED_RETENTION,
+            scheduler_params: SchedulerParams::default(),
+            interval_fuzz: true,
+            fuzz_seed: None,
+
+            learning_steps: LearningSteps::default(),
+            learning_steps_text: format_steps(&LearningSteps::default().learning),
+            relearning_steps_text: format_steps(&LearningSteps::default().relearning),
+        }
+    }
+}
+
+impl StudyOptions {
+    /// How cards are answered this session: sprints and exams ask one
+    /// card at a time and are marked right or wrong, so they type the
+    /// answer in typed mode and use multiple choice otherwise.
+    pub fn answer_mode(&self) -> StudyMode {
+        match (self.session_kind, self.study_mode) {
+            (SessionKind::Review, mode) | (_, mode @ StudyMode::Typed) => mode,
+            _ => StudyMode::MultipleChoice,
         }
     }
 }
@@ -77,14 +334,130 @@ pub fn draw_study_options_section(ui: &mut egui::Ui, study: &mut StudyOptions) {
     ui.label("Card content & layout:");
     ui.add_space(4.0);
 
-    ui.checkbox(
-        &mut study.show_term_first,
-        "Show term first (term → definition)",
-    );
+    ui.horizontal(|ui| {
+        ui.label("Ask:");
+        for directions in StudyDirections::ALL {
+            ui.radio_value(&mut study.directions, directions, directions.label());
+        }
+    });
+    if study.directions == StudyDirections::Both {
+        ui.checkbox(
+            &mut study.separate_siblings,
+            "Don't ask both directions of a card in the same session",
+        );
+    }
     ui.checkbox(
         &mut study.center_card,
         "Keep card centered in the study area",
     );
+    ui.horizontal(|ui| {
+        ui.label("Examples, notes & link:");
+        for details in DetailsVisibility::ALL {
+            ui.radio_value(&mut study.details, details, details.label());
+        }
+    });
+
+    ui.add_space(8.0);
+    ui.horizontal(|ui| {
+        ui.label("Answer mode:");
+        for mode in StudyMode::ALL {
+            ui.radio_value(&mut study.study_mode, mode, mode.label());
+        }
+    });
+
+    match study.study_mode {
+        StudyMode::MultipleChoice => {
+            ui.horizontal(|ui| {
+                ui.label("Answer choices:");
+                ui.add(
+                    egui::DragValue::new(&mut study.answer_count)
+                        .range(choice::MIN_OPTIONS..=choice::MAX_OPTIONS),
+                );
+            });
+            ui.horizontal(|ui| {
+                ui.label("Wrong choices:");
+                for strategy in DistractorStrategy::ALL {
+                    ui.radio_value(&mut study.distractor_strategy, strategy, strategy.label());
+                }
+            });
+        }
+        StudyMode::Typed => {
+            ui.horizontal(|ui| {
+                ui.label("Typed answers:");
+                for grading in TypedGrading::ALL {
+                    ui.radio_value(&mut study.typed_grading, grading, grading.label());
+                }
+            });
+            if study.typed_grading == TypedGrading::Tolerant {
+                ui.horizontal(|ui| {
+                    ui.label("Typos forgiven (at most one per four letters):");
+                    ui.add(egui::DragValue::new(&mut study.max_typos).range(1..=5));
+                });
+                ui.label("Answers with typos count as \"Hard\".");
+            }
+        }
+        StudyMode::Flip => {
+            ui.label("Space shows the answer; keys 1–4 grade it Again / Hard / Good / Easy.");
+        }
+        StudyMode::Matching => {
+            ui.horizontal(|ui| {
+                ui.label("Pairs per round:");
+                ui.add(
+                    egui::DragValue::new(&mut study.matching_pairs)
+                        .range(matching::MIN_PAIRS..=matching::MAX_PAIRS),
+                );
+            });
+            ui.label("Keys 1–8 pick a term, A–H a definition.");
+            ui.label("Matches only nudge the schedule; due cards stay due.");
+        }
+    }
+
+    ui.add_space(8.0);
+    ui.horizontal(|ui| {
+        ui.label("Session:");
+        for kind in SessionKind::ALL {
+            ui.radio_value(&mut study.session_kind, kind, kind.label());
+        }
+    });
+    match study.session_kind {
+        SessionKind::Review => {}
+        SessionKind::Sprint => {
+            ui.horizontal(|ui| {
+                ui.label("Sprint length:");
+                ui.add(
+                    egui::DragValue::new(&mut study.sprint_secs)
+                        .range(10..=600)
+                        .suffix(" s"),
+                );
+            });
+        }
+        SessionKind::Exam => {
+            ui.horizontal(|ui| {
+                ui.label("Exam questions:");
+                ui.add(egui::DragValue::new(&mut study.exam_questions).range(1..=500));
+            });
+            ui.label("Answers are only marked once the exam is over.");
+        }
+    }
+    if study.session_kind != SessionKind::Review {
+        ui.label("Sprints and exams draw from the whole deck, answer by multiple choice (or typing, in typed mode) and leave review progress alone.");
+    }
+
+    ui.add_space(8.0);
+    ui.checkbox(
+        &mut study.learn_until_correct,
+        "Learn until correct: missed cards come back this session",
+    );
+    if study.learn_until_correct {
+        ui.horizontal(|ui| {
+            ui.label("Bring a missed card back after:");
+            ui.add(
+                egui::DragValue::new(&mut study.requeue_gap)
+                    .range(1..=20)
+                    .suffix(" cards"),
+            );
+        });
+    }
 
     ui.add_space(8.0);
     ui.horizontal(|ui| {
@@ -101,6 +474,106 @@ pub fn draw_study_options_section(ui: &mut egui::Ui, study: &mut StudyOptions) {
     ui.separator();
     ui.add_space(8.0);
 
+    // === Scheduling ===
+    ui.label("Scheduling algorithm:");
+    ui.horizontal(|ui| {
+        for kind in SchedulerKind::ALL {
+            ui.radio_value(&mut study.scheduler, kind, kind.label());
+        }
+    });
+    ui.label("Decks can override this in their own file. Takes effect the next time a deck is opened.");
+
+    ui.add_space(8.0);
+    ui.horizontal(|ui| {
+        ui.label("Desired retention:");
+        ui.add(
+            egui::Slider::new(&mut study.desired_retention, 0.70..=0.97)
+                .step_by(0.01)
+                .custom_formatter(|r, _| format!("{:.0}%", r * 100.0)),
+        );
+    });
+    ui.label("Higher retention means shorter intervals and more reviews.");
+
+    ui.add_space(4.0);
+    ui.checkbox(&mut study.interval_fuzz, "Fuzz intervals (spread out cards learned together)");
+    if study.interval_fuzz {
+        ui.horizontal(|ui| {
+            let mut fixed = study.fuzz_seed.is_some();
+            if ui.checkbox(&mut fixed, "Fixed seed:").changed() {
+                study.fuzz_seed = fixed.then_some(0);
+            }
+            if let Some(seed) = study.fuzz_seed.as_mut() {
+                ui.add(egui::DragValue::new(seed));
+            }
+        });
+    }
+
+    ui.add_space(8.0);
+    ui.horizontal(|ui| {
+        ui.label("New cards per day:");
+        ui.add(egui::DragValue::new(&mut study.daily_limits.new_per_day).range(0..=9999));
+    });
+    ui.horizontal(|ui| {
+        ui.label("Reviews per day:");
+        ui.add(egui::DragValue::new(&mut study.daily_limits.reviews_per_day).range(0..=99999));
+    });
+    ui.horizontal(|ui| {
+        ui.label("Next day starts at:");
+        ui.add(
+            egui::DragValue::new(&mut study.day_rollover_hour)
+                .range(0..=23)
+                .custom_formatter(|h, _| format!("{h:02}:00")),
+        );
+    });
+    ui.label("Sessions only show cards that are due, up to the daily caps.");
+
+    ui.add_space(8.0);
+    ui.horizontal(|ui| {
+        ui.label("Leech after lapses:");
+        ui.add(egui::DragValue::new(&mut study.leech.threshold).range(0..=99));
+    });
+    ui.checkbox(&mut study.leech.suspend, "Suspend leeches automatically");
+    ui.label("Leeches are tagged \"leech\" and listed when the session ends (0 = off).");
+
+    ui.add_space(8.0);
+    ui.label("Answer grading (wrong answers are always \"Again\"):");
+    ui.horizontal(|ui| {
+        ui.label("Easy if answered within (s):");
+        ui.add(
+            egui::DragValue::new(&mut study.auto_grade.fast_secs)
+                .range(0.5..=60.0)
+                .speed(0.1),
+        );
+    });
+    ui.horizontal(|ui| {
+        ui.label("Hard if slower than (s):");
+        ui.add(
+            egui::DragValue::new(&mut study.auto_grade.slow_secs)
+                .range(0.5..=120.0)
+                .speed(0.1),
+        );
+    });
+    study.auto_grade.slow_secs = study.auto_grade.slow_secs.max(study.auto_grade.fast_secs);
+
+    ui.add_space(8.0);
+    ui.label("Steps (e.g. \"1m 10m 1d\"; leave empty to skip):");
+    steps_row(
+        ui,
+        "Learning steps:",
+        &mut study.learning_steps_text,
+        &mut study.learning_steps.learning,
+    );
+    steps_row(
+        ui,
+        "Relearning steps:",
+        &mut study.relearning_steps_text,
+        &mut study.learning_steps.relearning,
+    );
+
+    ui.add_space(16.0);
+    ui.separator();
+    ui.add_space(8.0);
+
     // === Card background ===
     ui.label("Card background color:");
     ui.horizontal(|ui| {
@@ -171,3 +644,17 @@ pub fn draw_study_options_section(ui: &mut egui::Ui, study: &mut StudyOptions) {
         });
     }
 }
+
+/// A text field for a step list. `steps` only changes when the text parses.
+fn steps_row(ui: &mut egui::Ui, label: &str, text: &mut String, steps: &mut Vec<u32>) {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        ui.text_edit_singleline(text);
+        match parse_steps(text) {
+            Ok(parsed) => *steps = parsed,
+            Err(e) => {
+                ui.colored_label(egui::Color32::LIGHT_RED, e);
+            }
+        }
+    });
+}
@@ -0,0 +1,101 @@
+// src/gui/app/screens/quiz_results_screen.rs
+
+use eframe::egui;
+
+use crate::gui::theme::Theme;
+use crate::srs::quiz::QuizReport;
+
+/// What the learner did on the results screen this frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuizResultsAction {
+    None,
+    ExportJson,
+    ExportCsv,
+    BackToList,
+}
+
+/// Draw a sprint or exam report: the score, then every question with
+/// the answer given, the expected answer and how long it took.
+pub fn draw_quiz_results_screen(ui: &mut egui::Ui, report: &QuizReport) -> QuizResultsAction {
+    let mut action = QuizResultsAction::None;
+
+    ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
+        ui.add_space(40.0);
+        ui.heading(format!("{} results — {}", report.kind.label(), report.deck));
+        ui.add_space(12.0);
+
+        egui::Frame::group(ui.style())
+            .rounding(egui::Rounding::same(8.0))
+            .fill(ui.visuals().extreme_bg_color)
+            .show(ui, |ui| {
+                ui.set_min_width(260.0);
+                ui.vertical_centered(|ui| {
+                    ui.label(format!(
+                        "Score: {}/{} ({:.0}%)",
+                        report.correct,
+                        report.questions,
+                        report.score_percent()
+                    ));
+                    let secs = report.duration_secs();
+                    let per_question = secs / report.questions.max(1) as f32;
+                    ui.label(format!(
+                        "Time: {secs:.0} s  |  {per_question:.1} s per question"
+                    ));
+                });
+            });
+
+        ui.add_space(16.0);
+
+        let table_height = (ui.available_height() - 120.0).max(160.0);
+        egui::ScrollArea::vertical()
+            .max_height(table_height)
+            .show(ui, |ui| {
+                egui::Grid::new("quiz-results")
+                    .striped(true)
+                    .spacing(egui::vec2(24.0, 6.0))
+                    .show(ui, |ui| {
+                        for header in ["#", "Question", "Your answer", "Answer", "", "Time"] {
+                            ui.strong(header);
+                        }
+                        ui.end_row();
+
+                        for a in &report.answers {
+                            ui.label(a.number.to_string());
+                            ui.label(&a.prompt);
+                            ui.label(&a.given);
+                            ui.label(&a.expected);
+                            if a.correct {
+                                ui.colored_label(Theme::CORRECT_OUTLINE, "✔");
+                            } else {
+                                ui.colored_label(Theme::WRONG_OUTLINE, "✖");
+                            }
+                            ui.label(format!("{:.1} s", a.secs));
+                            ui.end_row();
+                        }
+                    });
+
+                if report.answers.is_empty() {
+                    ui.small("No questions answered.");
+                }
+            });
+
+        ui.add_space(24.0);
+        ui.horizontal(|ui| {
+            if ui.button("💾 Export JSON…").clicked() {
+                action = QuizResultsAction::ExportJson;
+            }
+            if ui.button("💾 Export CSV…").clicked() {
+                action = QuizResultsAction::ExportCsv;
+            }
+            ui.add_space(24.0);
+            let back =
+                ui.add(egui::Button::new("← Back to Deck List").min_size(egui::vec2(240.0, 44.0)));
+            if back.clicked() {
+                action = QuizResultsAction::BackToList;
+            }
+        });
+        ui.add_space(20.0);
+    });
+
+    action
+}
@@ -1,158 +1,227 @@
 use eframe::egui;
 
-use crate::gui::app::screens::options_screen::StudyOptions;
+use crate::gui::app::screens::options_screen::{StudyMode, StudyOptions};
 use crate::gui::theme::Theme;
-use crate::model::Card;
+use crate::model::{Card, CardFlag, Direction};
+use crate::srs::cloze;
+use crate::srs::typed::{DiffChar, DiffKind};
+use crate::srs::Rating;
 
-/// Shared signature:
-/// - returns (clicked_term, back_to_list)
-type StudyResult = (Option<String>, bool);
+/// What the learner did on the study screen this frame.
+#[derive(Debug, Default)]
+pub struct StudyResult {
+    /// The option clicked, or the answer typed.
+    pub answer: Option<String>,
+    /// Asked to move on after reading the feedback.
+    pub advance: bool,
+    /// Flip mode: turned the card over.
+    pub reveal: bool,
+    /// Flip mode: graded themselves after revealing.
+    pub self_grade: Option<Rating>,
+    pub back_to_list: bool,
+}
+
+/// Typed-answer state owned by the app.
+pub struct TypedAnswer<'a> {
+    pub text: &'a mut String,
+    /// Diff of the submitted answer against the term (empty until answered).
+    pub diff: &'a [DiffChar],
+}
+
+/// Flip-card state owned by the app.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FlipCard {
+    /// Whether the answer side is showing.
+    pub revealed: bool,
+    /// Interval each grade (Again, Hard, Good, Easy) would give the card.
+    pub intervals: Option<[chrono::Duration; 4]>,
+}
+
+/// Something the learner did to the current card besides answering it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardAction {
+    /// Never show it again (until unsuspended in the deck builder).
+    Suspend,
+    /// Skip it until the next study day.
+    Bury,
+    /// Set or clear the card's flag.
+    Flag(Option<CardFlag>),
+}
+
+/// What the study screen shows this frame, owned by the app.
+pub struct StudyView<'a> {
+    pub current_card: Option<(&'a Card, Direction)>,
+    pub options: &'a [Card],
+    pub correct_term: Option<&'a str>,
+    pub wrong_term: Option<&'a str>,
+    pub feedback: &'a str,
+    pub progress: Option<(f32, String)>,
+    pub typed: TypedAnswer<'a>,
+    pub flip: FlipCard,
+    /// The current card has been answered.
+    pub answered: bool,
+    /// Feedback is waiting for the learner to move on.
+    pub awaiting_continue: bool,
+}
 
 /// Public entry point used from `app/mod.rs`.
 pub fn draw_study_screen(
     ui: &mut egui::Ui,
-    current_card: Option<&Card>,
-    options: &[Card],
-    correct_term: Option<&str>,
-    wrong_term: Option<&str>,
-    feedback: &str,
-    progress: f32,
-    reviewed: usize,
-    total: usize,
+    view: StudyView<'_>,
     study_opts: &StudyOptions,
 ) -> StudyResult {
-    // We keep this parameter so the call site compiles,
-    // but the card background color is handled in app/mod.rs.
-    let _ = study_opts;
+    // The card background color is handled in app/mod.rs; only the
+    // answer mode (which sprints and exams may override) is needed here.
+    draw_study_screen_inner(ui, view, study_opts.answer_mode())
+}
 
-    draw_study_screen_inner(
-        ui,
+fn draw_study_screen_inner(ui: &mut egui::Ui, view: StudyView<'_>, mode: StudyMode) -> StudyResult {
+    let StudyView {
         current_card,
         options,
         correct_term,
         wrong_term,
         feedback,
         progress,
-        reviewed,
-        total,
-    )
-}
-
-fn draw_study_screen_inner(
-    ui: &mut egui::Ui,
-    current_card: Option<&Card>,
-    options: &[Card],
-    correct_term: Option<&str>,
-    wrong_term: Option<&str>,
-    feedback: &str,
-    progress: f32,
-    reviewed: usize,
-    total: usize,
-) -> StudyResult {
+        typed,
+        flip,
+        answered,
+        awaiting_continue,
+    } = view;
     let mut clicked_term: Option<String> = None;
     let mut back_to_list = false;
+    let mut advance = false;
+    let mut revealed_now = false;
+    let mut self_grade = None;
 
     // ----------------------------------------------------
-    // Keyboard shortcuts: 1 / 2 / 3 / 4
+    // Keyboard shortcuts: 1 … 8
     // ----------------------------------------------------
-    let mut number_pressed: Option<usize> = None;
-    ui.ctx().input(|i| {
-        if i.key_pressed(egui::Key::Num1) {
-            number_pressed = Some(0);
-        }
-        if i.key_pressed(egui::Key::Num2) {
-            number_pressed = Some(1);
-        }
-        if i.key_pressed(egui::Key::Num3) {
-            number_pressed = Some(2);
-        }
-        if i.key_pressed(egui::Key::Num4) {
-            number_pressed = Some(3);
-        }
-    });
+    const NUMBER_KEYS: [egui::Key; 8] = [
+        egui::Key::Num1,
+        egui::Key::Num2,
+        egui::Key::Num3,
+        egui::Key::Num4,
+        egui::Key::Num5,
+        egui::Key::Num6,
+        egui::Key::Num7,
+        egui::Key::Num8,
+    ];
+    // Digits are part of typed answers, so only the button grid and the
+    // self-grading buttons have them.
+    let number_pressed: Option<usize> = match mode {
+        StudyMode::MultipleChoice | StudyMode::Flip | StudyMode::Matching => ui
+            .ctx()
+            .input(|i| NUMBER_KEYS.iter().position(|k| i.key_pressed(*k))),
+        StudyMode::Typed => None,
+    };
 
     let available = ui.available_size();
     let card_width = Theme::card_width(available.x);
-    let button_size = Theme::answer_button_size(card_width);
+    let columns = answer_grid_columns(options.len());
+    let button_size = Theme::answer_button_size(card_width, columns);
 
     // Center content within the window
     ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
         ui.set_width(card_width);
 
-        if let Some(card) = current_card {
+        if let Some((card, direction)) = current_card {
             // =======================
             // Definition header
             // =======================
             ui.vertical(|ui| {
                 ui.label(
-                    egui::RichText::new("Definition:")
+                    egui::RichText::new(prompt_label(card, direction))
                         .size(22.0)
                         .color(Theme::CARD_TEXT),
                 );
                 ui.add_space(8.0);
-                ui.label(
-                    egui::RichText::new(&card.definition)
-                        .size(32.0)
-                        .color(Theme::CARD_TEXT),
-                );
+                match card.cloze {
+                    // The blanked note, then the whole note once answered.
+                    Some(index) => {
+                        let reveal = answered || flip.revealed;
+                        ui.label(cloze_layout(&card.definition, index, reveal, 32.0));
+                    }
+                    None => {
+                        ui.label(
+                            egui::RichText::new(&card.definition)
+                                .size(32.0)
+                                .color(Theme::CARD_TEXT),
+                        );
+                    }
+                }
             });
 
             ui.add_space(40.0);
 
-            ui.label(
-                egui::RichText::new("Choose an answer:")
-                    .size(18.0)
-                    .color(Theme::CARD_TEXT),
-            );
-            ui.add_space(16.0);
+            match mode {
+                StudyMode::Typed => {
+                    clicked_term = draw_typed_answer(ui, card_width, typed, answered);
+                }
+                StudyMode::Flip => {
+                    let (reveal, grade) = draw_flip_answer(ui, card, flip, number_pressed);
+                    revealed_now = reveal;
+                    self_grade = grade;
+                }
+                // Matching rounds have a screen of their own
+                // (`matching_screen`); a lone card gets the grid.
+                StudyMode::MultipleChoice | StudyMode::Matching => {
+                    ui.label(
+                        egui::RichText::new("Choose an answer:")
+                            .size(18.0)
+                            .color(Theme::CARD_TEXT),
+                    );
+                    ui.add_space(16.0);
 
-            // =======================
-            // 2x2 answer grid
-            // =======================
-            egui::Grid::new("answer-grid")
-                .num_columns(2)
-                .spacing(egui::vec2(24.0, 20.0))
-                .show(ui, |ui| {
-                    for (idx, opt) in options.iter().enumerate() {
-                        let term_str = opt.term.as_str();
-
-                        let outline_color = if Some(term_str) == correct_term {
-                            Theme::CORRECT_OUTLINE
-                        } else if Some(term_str) == wrong_term {
-                            Theme::WRONG_OUTLINE
-                        } else {
-                            Theme::BUTTON_OUTLINE
-                        };
-
-                        let label = egui::RichText::new(&opt.term)
-                            .size(22.0)
-                            .color(Theme::BUTTON_TEXT);
-
-                        let button = egui::Button::new(label)
-                            .min_size(button_size)
-                            .fill(Theme::BUTTON_FILL)
-                            .stroke(egui::Stroke::new(2.0, outline_color))
-                            .rounding(egui::Rounding::same(12.0));
-
-                        let resp = ui.add(button);
-
-                        // Mouse click
-                        if resp.clicked() {
-                            clicked_term = Some(opt.term.clone());
-                        }
-
-                        // Keyboard press (1–4)
-                        if let Some(n) = number_pressed {
-                            if n == idx {
-                                clicked_term = Some(opt.term.clone());
-                            }
-                        }
+                    // =======================
+                    // Answer grid (2–4 columns depending on the option count)
+                    // =======================
+                    egui::Grid::new("answer-grid")
+                        .num_columns(columns)
+                        .spacing(egui::vec2(24.0, 20.0))
+                        .show(ui, |ui| {
+                            for (idx, opt) in options.iter().enumerate() {
+                                let term_str = opt.term.as_str();
 
-                        if idx % 2 == 1 {
-                            ui.end_row();
-                        }
-                    }
-                });
+                                let outline_color = if Some(term_str) == correct_term {
+                                    Theme::CORRECT_OUTLINE
+                                } else if Some(term_str) == wrong_term {
+                                    Theme::WRONG_OUTLINE
+                                } else {
+                                    Theme::BUTTON_OUTLINE
+                                };
+
+                                let label = egui::RichText::new(&opt.term)
+                                    .size(22.0)
+                                    .color(Theme::BUTTON_TEXT);
+
+                                let button = egui::Button::new(label)
+                                    .min_size(button_size)
+                                    .fill(Theme::BUTTON_FILL)
+                                    .stroke(egui::Stroke::new(2.0, outline_color))
+                                    .rounding(egui::Rounding::same(12.0));
+
+                                let resp = ui.add(button);
+
+                                // Mouse click
+                                if resp.clicked() {
+                                    clicked_term = Some(opt.term.clone());
+                                }
+
+                                // Keyboard press (1–8)
+                                if let Some(n) = number_pressed {
+                                    if n == idx {
+                                        clicked_term = Some(opt.term.clone());
+                                    }
+                                }
+
+                                if idx % columns == columns - 1 {
+                                    ui.end_row();
+                                }
+                            }
+                        });
+                }
+            }
 
             ui.add_space(24.0);
 
@@ -169,15 +238,33 @@ fn draw_study_screen_inner(
                 });
             }
 
+            // Feedback that needs reading (e.g. a typo diff) waits for
+            // the learner instead of auto-advancing.
+            if awaiting_continue {
+                ui.add_space(12.0);
+                let label = egui::RichText::new("Continue (Enter)")
+                    .size(20.0)
+                    .color(Theme::BUTTON_TEXT);
+                let button = egui::Button::new(label)
+                    .min_size(egui::vec2(220.0, 44.0))
+                    .fill(Theme::BUTTON_FILL)
+                    .stroke(egui::Stroke::new(2.0, Theme::BUTTON_OUTLINE))
+                    .rounding(egui::Rounding::same(10.0));
+                let enter = ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if ui.add(button).clicked() || enter {
+                    advance = true;
+                }
+            }
+
             ui.add_space(24.0);
 
             // =======================
             // Progress bar
             // =======================
-            if total > 0 {
-                let bar = egui::ProgressBar::new(progress)
+            if let Some((fraction, text)) = progress {
+                let bar = egui::ProgressBar::new(fraction)
                     .desired_width(card_width - 40.0)
-                    .text(format!("{reviewed}/{total}"));
+                    .text(text);
                 ui.add(bar);
             }
 
@@ -215,5 +302,382 @@ fn draw_study_screen_inner(
         }
     });
 
-    (clicked_term, back_to_list)
+    StudyResult {
+        answer: clicked_term,
+        advance,
+        reveal: revealed_now,
+        self_grade,
+        back_to_list,
+    }
+}
+
+/// Flip mode: "Show answer" until revealed (Space or click), then the
+/// term and the four self-grading buttons (keys 1–4).
+///
+/// Returns (revealed this frame, grade picked).
+fn draw_flip_answer(
+    ui: &mut egui::Ui,
+    card: &Card,
+    flip: FlipCard,
+    number_pressed: Option<usize>,
+) -> (bool, Option<Rating>) {
+    if !flip.revealed {
+        let label = egui::RichText::new("Show answer (Space)")
+            .size(22.0)
+            .color(Theme::BUTTON_TEXT);
+        let button = egui::Button::new(label)
+            .min_size(egui::vec2(260.0, 56.0))
+            .fill(Theme::BUTTON_FILL)
+            .stroke(egui::Stroke::new(2.0, Theme::BUTTON_OUTLINE))
+            .rounding(egui::Rounding::same(12.0));
+
+        let space = ui.input(|i| i.key_pressed(egui::Key::Space));
+        return (ui.add(button).clicked() || space, None);
+    }
+
+    ui.label(
+        egui::RichText::new("Answer:")
+            .size(22.0)
+            .color(Theme::CARD_TEXT),
+    );
+    ui.add_space(8.0);
+    ui.label(
+        egui::RichText::new(&card.term)
+            .size(32.0)
+            .color(Theme::CARD_TEXT),
+    );
+    ui.add_space(24.0);
+
+    let mut grade = number_pressed.and_then(|n| Rating::ALL.get(n).copied());
+    ui.horizontal(|ui| {
+        for (idx, rating) in Rating::ALL.into_iter().enumerate() {
+            let mut text = format!("{} {}", idx + 1, rating.label());
+            if let Some(intervals) = flip.intervals {
+                text.push_str(&format!("\n{}", format_interval(intervals[idx])));
+            }
+            let outline = if rating == Rating::Again {
+                Theme::WRONG_OUTLINE
+            } else {
+                Theme::BUTTON_OUTLINE
+            };
+            let button = egui::Button::new(
+                egui::RichText::new(text)
+                    .size(20.0)
+                    .color(Theme::BUTTON_TEXT),
+            )
+            .min_size(egui::vec2(120.0, 56.0))
+            .fill(Theme::BUTTON_FILL)
+            .stroke(egui::Stroke::new(2.0, outline))
+            .rounding(egui::Rounding::same(12.0));
+
+            if ui.add(button).clicked() {
+                grade = Some(rating);
+            }
+        }
+    });
+
+    (false, grade)
+}
+
+/// Short interval label for the grading buttons: "<1m", "10m", "3h",
+/// "4d", "2.1mo", "1.5y".
+pub fn format_interval(interval: chrono::Duration) -> String {
+    let minutes = interval.num_minutes();
+    let days = interval.num_seconds() as f64 / 86_400.0;
+
+    if minutes < 1 {
+        "<1m".to_string()
+    } else if minutes < 60 {
+        format!("{minutes}m")
+    } else if minutes < 24 * 60 {
+        format!("{}h", minutes / 60)
+    } else if days < 30.0 {
+        format!("{}d", days.round())
+    } else if days < 365.0 {
+        format!("{:.1}mo", days / 30.0)
+    } else {
+        format!("{:.1}y", days / 365.0)
+    }
+}
+
+/// Text box for typing the term (Enter or "Check" submits), then the
+/// diff against the expected answer once it's been graded.
+fn draw_typed_answer(
+    ui: &mut egui::Ui,
+    card_width: f32,
+    typed: TypedAnswer<'_>,
+    answered: bool,
+) -> Option<String> {
+    let mut submitted = None;
+
+    ui.label(
+        egui::RichText::new("Type the answer:")
+            .size(18.0)
+            .color(Theme::CARD_TEXT),
+    );
+    ui.add_space(16.0);
+
+    ui.horizontal(|ui| {
+        let edit = egui::TextEdit::singleline(typed.text)
+            .font(egui::FontId::proportional(26.0))
+            .desired_width(card_width * 0.7)
+            .interactive(!answered);
+        let resp = ui.add(edit);
+
+        let enter = resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if !answered && !resp.has_focus() && !enter {
+            resp.request_focus();
+        }
+
+        let check = ui.add_enabled(
+            !answered,
+            egui::Button::new(egui::RichText::new("Check").size(20.0))
+                .min_size(egui::vec2(100.0, 40.0)),
+        );
+        if !answered && (enter || check.clicked()) && !typed.text.trim().is_empty() {
+            submitted = Some(typed.text.trim().to_string());
+        }
+    });
+
+    if answered && typed.diff.iter().any(|c| c.kind != DiffKind::Same) {
+        ui.add_space(12.0);
+        ui.label(diff_layout(typed.diff, 24.0));
+        ui.label(
+            egui::RichText::new("red = typed by mistake, green = missing")
+                .size(14.0)
+                .color(Theme::CARD_TEXT),
+        );
+    }
+
+    submitted
+}
+
+/// Typed-vs-expected diff as one line: mistakes struck through in red,
+/// missing letters underlined in green.
+fn diff_layout(diff: &[DiffChar], size: f32) -> egui::text::LayoutJob {
+    let mut job = egui::text::LayoutJob::default();
+    let font_id = egui::FontId::monospace(size);
+
+    for c in diff {
+        let mut format = egui::TextFormat::simple(font_id.clone(), Theme::CARD_TEXT);
+        match c.kind {
+            DiffKind::Same => {}
+            DiffKind::Extra => {
+                format.color = Theme::WRONG_OUTLINE;
+                format.strikethrough = egui::Stroke::new(2.0, Theme::WRONG_OUTLINE);
+            }
+            DiffKind::Missing => {
+                format.color = Theme::CORRECT_OUTLINE;
+                format.underline = egui::Stroke::new(2.0, Theme::CORRECT_OUTLINE);
+            }
+        }
+        job.append(&c.ch.to_string(), 0.0, format);
+    }
+    job
+}
+
+/// A cloze note with deletion `index` blanked out, or (once `revealed`)
+/// filled in and underlined in green. Other deletions are always filled in.
+fn cloze_layout(text: &str, index: u32, revealed: bool, size: f32) -> egui::text::LayoutJob {
+    let mut job = egui::text::LayoutJob::default();
+    let plain = egui::TextFormat::simple(egui::FontId::proportional(size), Theme::CARD_TEXT);
+
+    for segment in cloze::parse(text) {
+        match segment {
+            cloze::Segment::Text(t) => job.append(t, 0.0, plain.clone()),
+            cloze::Segment::Deletion {
+                index: i, answer, ..
+            } if i != index => job.append(answer, 0.0, plain.clone()),
+            cloze::Segment::Deletion { answer, hint, .. } => {
+                let mut format = plain.clone();
+                format.color = Theme::CORRECT_OUTLINE;
+                if revealed {
+                    format.underline = egui::Stroke::new(2.0, Theme::CORRECT_OUTLINE);
+                    job.append(answer, 0.0, format);
+                } else {
+                    job.append(&cloze::blank(hint), 0.0, format);
+                }
+            }
+        }
+    }
+    job
+}
+
+/// Header over the prompt. Cards come in already oriented (see
+/// `Direction::orient`), so the prompt is always `card.definition`.
+fn prompt_label(card: &Card, direction: Direction) -> &'static str {
+    if card.cloze.is_some() {
+        return "Fill in the blank:";
+    }
+    match direction {
+        Direction::Forward => "Term:",
+        Direction::Reverse => "Definition:",
+    }
+}
+
+/// Columns for the answer grid: one row for 2–3 options, 2×2 for four,
+/// then two rows of three or four.
+fn answer_grid_columns(count: usize) -> usize {
+    match count {
+        0..=3 => count.max(1),
+        4 => 2,
+        5 | 6 => 3,
+        _ => 4,
+    }
+}
+
+/// The card's image and, for audio, a replay button (also Ctrl+R), shown
+/// above the prompt. Returns `true` if the replay button was clicked.
+pub fn draw_card_media(
+    ui: &mut egui::Ui,
+    image: Option<&egui::TextureHandle>,
+    has_audio: bool,
+) -> bool {
+    let mut replay = false;
+
+    ui.vertical_centered(|ui| {
+        if let Some(texture) = image {
+            let max = egui::vec2(Theme::card_width(ui.available_width()), 240.0);
+            ui.add(egui::Image::new(texture).max_size(max).rounding(8.0));
+            ui.add_space(12.0);
+        }
+        if has_audio {
+            replay = ui
+                .button(egui::RichText::new("🔊 Replay (Ctrl+R)").size(18.0))
+                .clicked();
+            ui.add_space(12.0);
+        }
+    });
+
+    replay
+}
+
+/// Collapsible panel with the card's examples, notes and hyperlink (which
+/// opens in the system browser if it is a web link, and is only shown
+/// otherwise). Draws nothing if the card has none.
+pub fn draw_card_details(ui: &mut egui::Ui, card: &Card) {
+    if !card.has_details() {
+        return;
+    }
+
+    egui::CollapsingHeader::new(
+        egui::RichText::new("📖 Details")
+            .size(18.0)
+            .color(Theme::CARD_TEXT),
+    )
+    .id_source("study-card-details")
+    .default_open(true)
+    .show(ui, |ui| {
+        if !card.examples.is_empty() {
+            ui.label(egui::RichText::new("Examples:").strong());
+            for example in &card.examples {
+                ui.label(format!("• {example}"));
+            }
+            ui.add_space(6.0);
+        }
+        if let Some(notes) = &card.notes {
+            ui.label(egui::RichText::new("Notes:").strong());
+            ui.label(notes);
+            ui.add_space(6.0);
+        }
+        if let Some(url) = &card.hyperlink {
+            if is_web_link(url) {
+                ui.hyperlink_to(format!("🔗 {url}"), url);
+            } else {
+                ui.label(format!("🔗 {url}"));
+            }
+        }
+    });
+}
+
+/// Whether `url` is an http(s) link. Decks are shared, so other schemes
+/// (`file://`, custom app handlers, ...) are never opened from a card.
+fn is_web_link(url: &str) -> bool {
+    let url = url.trim_start();
+    ["http://", "https://"].iter().any(|scheme| {
+        url.get(..scheme.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(scheme))
+    })
+}
+
+/// Suspend / bury / flag row shown under the study card.
+pub fn draw_card_actions(ui: &mut egui::Ui, flag: Option<CardFlag>) -> Option<CardAction> {
+    let mut action = None;
+
+    ui.horizontal(|ui| {
+        let mut new_flag = flag;
+        if flag_picker(ui, "study-card-flag", &mut new_flag) {
+            action = Some(CardAction::Flag(new_flag));
+        }
+
+        ui.add_space(12.0);
+        if ui
+            .button("⏸ Suspend")
+            .on_hover_text("Stop showing this card")
+            .clicked()
+        {
+            action = Some(CardAction::Suspend);
+        }
+        if ui
+            .button("⏭ Bury")
+            .on_hover_text("Skip this card until tomorrow")
+            .clicked()
+        {
+            action = Some(CardAction::Bury);
+        }
+    });
+
+    action
+}
+
+/// Combo box for picking a card flag. Returns `true` if it changed.
+pub fn flag_picker(
+    ui: &mut egui::Ui,
+    id_source: impl std::hash::Hash,
+    flag: &mut Option<CardFlag>,
+) -> bool {
+    let selected = match flag {
+        Some(f) => egui::RichText::new(format!("⚑ {}", f.label())).color(Theme::flag_color(*f)),
+        None => egui::RichText::new("⚐ No flag"),
+    };
+
+    let before = *flag;
+    egui::ComboBox::from_id_source(id_source)
+        .selected_text(selected)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(flag, None, "⚐ No flag");
+            for f in CardFlag::ALL {
+                let text =
+                    egui::RichText::new(format!("⚑ {}", f.label())).color(Theme::flag_color(f));
+                ui.selectable_value(flag, Some(f), text);
+            }
+        });
+    *flag != before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn intervals_are_labelled_in_the_largest_fitting_unit() {
+        assert_eq!(format_interval(Duration::seconds(30)), "<1m");
+        assert_eq!(format_interval(Duration::minutes(10)), "10m");
+        assert_eq!(format_interval(Duration::minutes(150)), "2h");
+        assert_eq!(format_interval(Duration::hours(36)), "2d");
+        assert_eq!(format_interval(Duration::days(29)), "29d");
+        assert_eq!(format_interval(Duration::days(63)), "2.1mo");
+        assert_eq!(format_interval(Duration::days(548)), "1.5y");
+    }
+
+    #[test]
+    fn only_web_links_are_clickable() {
+        assert!(is_web_link("https://example.com/cat"));
+        assert!(is_web_link("HTTP://example.com"));
+        assert!(!is_web_link("file:///etc/passwd"));
+        assert!(!is_web_link("javascript:alert(1)"));
+        assert!(!is_web_link("example.com"));
+        assert!(!is_web_link("http:"));
+    }
 }
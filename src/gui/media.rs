@@ -0,0 +1,202 @@
+// src/gui/media.rs
+//
+// Card media: images (PNG, JPEG, WebP, animated GIF) drawn on the study
+// card, and audio played through the `SoundManager`.
+//
+// Decks store media paths relative to the deck file. Images are decoded
+// and uploaded once per file; every GIF frame becomes a texture of its own.
+
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use eframe::egui::{self, ColorImage, TextureHandle, TextureOptions};
+use image::AnimationDecoder;
+
+/// GIF frames without a delay of their own are shown this long.
+const DEFAULT_FRAME_SECS: f32 = 0.1;
+
+/// What a media file is, going by its extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaKind {
+    Image,
+    Audio,
+}
+
+impl MediaKind {
+    /// `None` for files the study screen can't show or play (e.g. video).
+    pub fn of(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "png" | "jpg" | "jpeg" | "webp" | "gif" => Some(MediaKind::Image),
+            "ogg" | "mp3" | "wav" | "flac" => Some(MediaKind::Audio),
+            _ => None,
+        }
+    }
+}
+
+/// A card's media path, relative to the folder of the deck it came from
+/// (absolute paths are kept as they are).
+pub fn resolve(deck_dir: Option<&Path>, media: &str) -> PathBuf {
+    let path = Path::new(media.trim());
+    match deck_dir {
+        Some(dir) if path.is_relative() => dir.join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// A decoded image: one frame, or a GIF's frames with how long each shows.
+pub struct LoadedImage {
+    textures: Vec<TextureHandle>,
+    delays: Vec<f32>,
+}
+
+impl LoadedImage {
+    pub fn is_animated(&self) -> bool {
+        self.textures.len() > 1
+    }
+
+    /// The frame showing `secs` into the animation (looping), and how long
+    /// until the next one.
+    pub fn frame_at(&self, secs: f32) -> (&TextureHandle, f32) {
+        let (index, left) = frame_at(&self.delays, secs);
+        (&self.textures[index], left)
+    }
+}
+
+/// Index of the frame showing `secs` into an animation whose frames show
+/// for `delays` seconds each (looping), and how long until the next one.
+/// A single frame shows forever.
+fn frame_at(delays: &[f32], secs: f32) -> (usize, f32) {
+    if delays.len() < 2 {
+        return (0, f32::INFINITY);
+    }
+
+    let mut t = secs % delays.iter().sum::<f32>();
+    for (index, delay) in delays.iter().enumerate() {
+        if t < *delay {
+            return (index, delay - t);
+        }
+        t -= delay;
+    }
+    // Only reachable through float rounding at the very end of a loop.
+    let last = delays.len() - 1;
+    (last, delays[last])
+}
+
+/// Textures of the card images seen so far, by path. Files that failed
+/// to load are remembered too, so they are only tried (and reported) once.
+#[derive(Default)]
+pub struct MediaCache {
+    images: HashMap<PathBuf, Option<LoadedImage>>,
+}
+
+impl MediaCache {
+    /// The image at `path`, decoding and uploading it on first use.
+    pub fn image(&mut self, ctx: &egui::Context, path: &Path) -> Option<&LoadedImage> {
+        self.images
+            .entry(path.to_path_buf())
+            .or_insert_with(|| match load_image(ctx, path) {
+                Ok(image) => Some(image),
+                Err(e) => {
+                    eprintln!(
+                        "MorFlash: failed to load card image {}: {e:#}",
+                        path.display()
+                    );
+                    None
+                }
+            })
+            .as_ref()
+    }
+
+    /// Drop every texture (e.g. when another deck is opened).
+    pub fn clear(&mut self) {
+        self.images.clear();
+    }
+}
+
+fn load_image(ctx: &egui::Context, path: &Path) -> anyhow::Result<LoadedImage> {
+    let bytes = std::fs::read(path)?;
+    let id = path.to_string_lossy();
+
+    let is_gif = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("gif"));
+    let (mut textures, mut delays) = (Vec::new(), Vec::new());
+    if is_gif {
+        let decoder = image::codecs::gif::GifDecoder::new(Cursor::new(&bytes))?;
+        for (i, frame) in decoder.into_frames().enumerate() {
+            let frame = frame?;
+            let (num, den) = frame.delay().numer_denom_ms();
+            let secs = num as f32 / den.max(1) as f32 / 1000.0;
+            let secs = if secs > 0.0 { secs } else { DEFAULT_FRAME_SECS };
+            textures.push(upload(ctx, &format!("{id}#{i}"), frame.into_buffer()));
+            delays.push(secs);
+        }
+    } else {
+        let image = image::load_from_memory(&bytes)?.to_rgba8();
+        textures.push(upload(ctx, &id, image));
+        delays.push(f32::INFINITY);
+    }
+
+    if textures.is_empty() {
+        anyhow::bail!("no frames");
+    }
+    Ok(LoadedImage { textures, delays })
+}
+
+fn upload(ctx: &egui::Context, id: &str, image: image::RgbaImage) -> TextureHandle {
+    let size = [image.width() as usize, image.height() as usize];
+    let color_image = ColorImage::from_rgba_unmultiplied(size, image.as_raw());
+    ctx.load_texture(id, color_image, TextureOptions::LINEAR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_media_resolves_against_the_deck_folder() {
+        let deck_dir = Path::new("decks/animals");
+        assert_eq!(
+            resolve(Some(deck_dir), " media/cat.png "),
+            Path::new("decks/animals/media/cat.png")
+        );
+        assert_eq!(resolve(None, "media/cat.png"), Path::new("media/cat.png"));
+
+        let absolute = std::env::temp_dir().join("cat.png");
+        let media = absolute.to_string_lossy();
+        assert_eq!(resolve(Some(deck_dir), &media), absolute);
+    }
+
+    #[test]
+    fn media_kind_goes_by_extension_in_any_case() {
+        assert_eq!(
+            MediaKind::of(Path::new("a/cat.PNG")),
+            Some(MediaKind::Image)
+        );
+        assert_eq!(MediaKind::of(Path::new("cat.Gif")), Some(MediaKind::Image));
+        assert_eq!(MediaKind::of(Path::new("meow.OGG")), Some(MediaKind::Audio));
+        assert_eq!(MediaKind::of(Path::new("meow.mp3")), Some(MediaKind::Audio));
+        assert_eq!(MediaKind::of(Path::new("clip.mp4")), None);
+        assert_eq!(MediaKind::of(Path::new("README")), None);
+    }
+
+    #[test]
+    fn frames_loop_by_their_delays() {
+        let delays = [0.5, 0.25, 0.25];
+        assert_eq!(frame_at(&delays, 0.0), (0, 0.5));
+        assert_eq!(frame_at(&delays, 0.25), (0, 0.25));
+        assert_eq!(frame_at(&delays, 0.5), (1, 0.25));
+        assert_eq!(frame_at(&delays, 0.875), (2, 0.125));
+        // Second time round.
+        assert_eq!(frame_at(&delays, 1.0), (0, 0.5));
+        assert_eq!(frame_at(&delays, 1.5), (1, 0.25));
+    }
+
+    #[test]
+    fn a_still_image_never_changes() {
+        assert_eq!(frame_at(&[f32::INFINITY], 0.0), (0, f32::INFINITY));
+        assert_eq!(frame_at(&[f32::INFINITY], 1e6), (0, f32::INFINITY));
+    }
+}
@@ -1,3 +1,4 @@
 pub mod app;
+pub mod media;
 pub mod sound;
 pub mod theme;
@@ -1,7 +1,11 @@
 // src/gui/sound.rs
 
 use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink};
-use std::{collections::HashMap, io::Cursor, path::Path};
+use std::{
+    collections::{HashMap, HashSet},
+    io::Cursor,
+    path::Path,
+};
 
 pub type SoundId = String;
 
@@ -9,6 +13,9 @@ pub struct SoundManager {
     _stream: OutputStream,
     handle: OutputStreamHandle,
     sounds: HashMap<SoundId, Vec<u8>>,
+    /// Files `play_file` couldn't load, so they are only tried (and
+    /// reported) once.
+    failed: HashSet<SoundId>,
     volume: f32,
     enabled: bool,
 }
@@ -24,6 +31,7 @@ impl SoundManager {
             _stream: stream,
             handle,
             sounds: HashMap::new(),
+            failed: HashSet::new(),
             volume: 1.0,
             enabled: true,
         })
@@ -85,7 +93,11 @@ impl SoundManager {
         if !self.enabled {
             return;
         }
+        self.play_loaded(id);
+    }
 
+    /// Play a loaded sound, whether or not sounds are enabled.
+    fn play_loaded(&self, id: &str) {
         let Some(bytes) = self.sounds.get(id) else {
             eprintln!("MorFlash: unknown sound id '{id}'");
             return;
@@ -107,7 +119,27 @@ impl SoundManager {
         sink.detach();
     }
 
+    /// Play an audio file (e.g. a card's media), loading it on first use.
+    /// Card audio is content rather than a sound effect, so it plays even
+    /// with sound effects turned off.
+    pub fn play_file(&mut self, path: &Path) {
+        let id = path.to_string_lossy().into_owned();
+        if self.failed.contains(&id) {
+            return;
+        }
+        if !self.sounds.contains_key(&id) {
+            self.load_sound(id.clone(), path);
+            if !self.sounds.contains_key(&id) {
+                self.failed.insert(id);
+                return;
+            }
+        }
+
+        self.play_loaded(&id);
+    }
+
     pub fn clear(&mut self) {
         self.sounds.clear();
+        self.failed.clear();
     }
 }
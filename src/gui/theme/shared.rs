@@ -1,5 +1,6 @@
 // src/gui/theme.rs
 use crate::gui::app::screens::options_screen::FontChoice;
+use crate::model::CardFlag;
 use eframe::egui;
 
 pub struct Theme;
@@ -60,6 +61,17 @@ impl Theme {
     pub const CORRECT_OUTLINE: egui::Color32 = egui::Color32::from_rgb(80, 200, 140);
     pub const WRONG_OUTLINE: egui::Color32 = egui::Color32::from_rgb(230, 90, 120);
 
+    /// Colour used to show a card's flag.
+    pub fn flag_color(flag: CardFlag) -> egui::Color32 {
+        match flag {
+            CardFlag::Red => egui::Color32::from_rgb(235, 80, 80),
+            CardFlag::Orange => egui::Color32::from_rgb(245, 160, 60),
+            CardFlag::Green => egui::Color32::from_rgb(90, 200, 110),
+            CardFlag::Blue => egui::Color32::from_rgb(80, 150, 240),
+            CardFlag::Purple => egui::Color32::from_rgb(178, 102, 255),
+        }
+    }
+
     // ===============================================================
     //  LAYOUT CONSTANTS
     // ===============================================================
@@ -74,8 +86,9 @@ impl Theme {
         available_width.clamp(480.0, 1200.0)
     }
 
-    pub fn answer_button_size(card_width: f32) -> egui::Vec2 {
-        let w = card_width * 0.45;
+    /// Size of one answer button when `columns` of them share a row.
+    pub fn answer_button_size(card_width: f32, columns: usize) -> egui::Vec2 {
+        let w = card_width * 0.9 / columns.max(1) as f32;
         let h = 56.0;
         egui::vec2(w, h)
     }
@@ -8,7 +8,8 @@ use anyhow::{anyhow, Context, Result};
 use rusqlite::Connection;
 use zip::ZipArchive;
 
-use crate::model::Deck;
+use crate::model::{Card, Deck};
+use crate::srs::cloze;
 use super::deck_from_txt; // reuse the existing TXT importer
 
 /// Import an Anki `.apkg` file or an *unzipped* APKG folder into a `Deck`.
@@ -24,6 +25,9 @@ use super::deck_from_txt; // reuse the existing TXT importer
 /// - Treat field 0 as term, field 1 as definition
 /// - Strip simple Anki markup like `[sound:...]` and basic `[anki:tts]` blocks
 /// - Convert to a synthetic TXT deck and run `deck_from_txt`
+/// - Cloze notes (field 0 has `{{c1::...}}` deletions) are kept aside and
+///   added as one card per deletion (see `srs::cloze`), with field 1
+///   ("Back Extra") as the cards' notes
 pub fn deck_from_apkg(path: &Path) -> Result<Deck> {
     if path.is_dir() {
         // User has already unzipped the APKG into a folder.
@@ -102,7 +106,7 @@ fn deck_from_apkg_zip(path: &Path) -> Result<Deck> {
     let conn = Connection::open(&tmp_path).context("Failed to open APKG SQLite DB")?;
 
     // Build synthetic TXT from this DB.
-    let synthetic_txt = synthetic_txt_from_notes(&conn)?;
+    let notes = synthetic_txt_from_notes(&conn)?;
 
     // Best-effort cleanup of temp file (ignore errors).
     let _ = std::fs::remove_file(&tmp_path);
@@ -110,7 +114,7 @@ fn deck_from_apkg_zip(path: &Path) -> Result<Deck> {
     // ----------------------------------------
     // 5. Build Deck via existing TXT importer
     // ----------------------------------------
-    finalize_deck_from_synthetic_txt(path, &synthetic_txt)
+    finalize_deck_from_synthetic_txt(path, &notes)
 }
 
 /// Handle the case where the user has unzipped the APKG into a directory.
@@ -149,9 +153,9 @@ fn deck_from_unzipped_apkg_dir(dir: &Path) -> Result<Deck> {
     let conn = Connection::open(&db_path)
         .with_context(|| format!("Failed to open SQLite DB at {}", db_path.display()))?;
 
-    let synthetic_txt = synthetic_txt_from_notes(&conn)?;
+    let notes = synthetic_txt_from_notes(&conn)?;
 
-    finalize_deck_from_synthetic_txt(dir, &synthetic_txt)
+    finalize_deck_from_synthetic_txt(dir, &notes)
 }
 
 /// Very small, zero-dependency cleaner for some common Anki markup.
@@ -209,13 +213,21 @@ fn strip_anki_markup(s: &str) -> String {
     cleaned
 }
 
+/// Notes read from an APKG database.
+struct ApkgNotes {
+    /// Term/definition notes as tab-separated TXT lines.
+    synthetic_txt: String,
+    /// Text of each cloze note, and its "Back Extra" field (if not empty).
+    cloze: Vec<(String, Option<String>)>,
+}
+
 /// Shared helper: read `notes.flds` and convert to a synthetic TXT deck.
 ///
 /// In Anki, `notes.flds` is a single string with `\x1F` separators.
 /// We treat:
-///   field[0] → term
-///   field[1] → definition
-fn synthetic_txt_from_notes(conn: &Connection) -> Result<String> {
+///   field[0] → term (or the cloze text of a cloze note)
+///   field[1] → definition (for cloze notes: "Back Extra", kept as notes)
+fn synthetic_txt_from_notes(conn: &Connection) -> Result<ApkgNotes> {
     let mut stmt = conn
         .prepare("SELECT flds FROM notes")
         .context("Failed to prepare notes query")?;
@@ -227,6 +239,7 @@ fn synthetic_txt_from_notes(conn: &Connection) -> Result<String> {
         .context("Failed to iterate notes from APKG DB")?;
 
     let mut synthetic_txt = String::new();
+    let mut cloze_notes = Vec::new();
 
     for row in rows {
         let flds = row.context("Failed to read a `flds` row from notes")?;
@@ -238,6 +251,13 @@ fn synthetic_txt_from_notes(conn: &Connection) -> Result<String> {
         let term = strip_anki_markup(term_raw);
         let definition = strip_anki_markup(definition_raw);
 
+        if cloze::is_cloze(&term) {
+            let extra = definition.trim();
+            let extra = (!extra.is_empty()).then(|| extra.to_string());
+            cloze_notes.push((term.trim().to_string(), extra));
+            continue;
+        }
+
         // Skip totally empty rows
         if term.trim().is_empty() && definition.trim().is_empty() {
             continue;
@@ -249,22 +269,33 @@ fn synthetic_txt_from_notes(conn: &Connection) -> Result<String> {
         synthetic_txt.push('\n');
     }
 
-    if synthetic_txt.trim().is_empty() {
+    if synthetic_txt.trim().is_empty() && cloze_notes.is_empty() {
         return Err(anyhow!(
             "APKG import produced no usable notes (no term/definition pairs found)"
         ));
     }
 
-    Ok(synthetic_txt)
+    Ok(ApkgNotes {
+        synthetic_txt,
+        cloze: cloze_notes,
+    })
 }
 
-/// Finalize into a MorFlash `Deck` using the existing TXT importer.
-fn finalize_deck_from_synthetic_txt(source_path: &Path, synthetic_txt: &str) -> Result<Deck> {
+/// Finalize into a MorFlash `Deck` using the existing TXT importer, then
+/// append the cloze cards.
+fn finalize_deck_from_synthetic_txt(source_path: &Path, notes: &ApkgNotes) -> Result<Deck> {
     let deck_name = source_path
         .file_stem()
         .and_then(|s| s.to_str())
         .unwrap_or("Imported Anki deck");
 
-    let deck = deck_from_txt(deck_name, None, synthetic_txt);
+    let mut deck = deck_from_txt(deck_name, None, &notes.synthetic_txt);
+    for (note, extra) in &notes.cloze {
+        let first_id = deck.cards.len() as u64 + 1;
+        deck.cards.extend(cloze::cards(note, first_id..).into_iter().map(|card| Card {
+            notes: extra.clone(),
+            ..card
+        }));
+    }
     Ok(deck)
 }
@@ -69,6 +69,7 @@ fn deck_from_flexible_csv(raw: &str) -> anyhow::Result<Deck> {
             id: next_id,
             term: term.to_string(),
             definition: definition.to_string(),
+            ..Default::default()
         });
         next_id += 1;
         row_index += 1;
@@ -78,11 +79,7 @@ fn deck_from_flexible_csv(raw: &str) -> anyhow::Result<Deck> {
         anyhow::bail!("No cards parsed from CSV");
     }
 
-    Ok(Deck {
-        name: "CSV Deck".to_string(),
-        description: None,
-        cards,
-    })
+    Ok(Deck::new("CSV Deck", None, cards))
 }
 
 /// Very simple header detection for common schemas.
@@ -121,6 +118,7 @@ fn deck_from_legacy_csv(raw: &str) -> anyhow::Result<Deck> {
             id: next_id,
             term: term.to_string(),
             definition: definition.to_string(),
+            ..Default::default()
         });
         next_id += 1;
     }
@@ -129,9 +127,5 @@ fn deck_from_legacy_csv(raw: &str) -> anyhow::Result<Deck> {
         anyhow::bail!("No cards parsed from legacy CSV");
     }
 
-    Ok(Deck {
-        name: "CSV Deck".to_string(),
-        description: None,
-        cards,
-    })
+    Ok(Deck::new("CSV Deck", None, cards))
 }
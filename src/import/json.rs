@@ -67,6 +67,7 @@ pub fn deck_from_json_cards_array(raw: &str) -> anyhow::Result<Deck> {
                 id: next_id,
                 term: t.to_string(),
                 definition: d.to_string(),
+                ..Default::default()
             });
             next_id += 1;
         }
@@ -76,11 +77,7 @@ pub fn deck_from_json_cards_array(raw: &str) -> anyhow::Result<Deck> {
         anyhow::bail!("no cards found");
     }
 
-    Ok(Deck {
-        name: "JSON Cards Deck".to_string(),
-        description: None,
-        cards,
-    })
+    Ok(Deck::new("JSON Cards Deck", None, cards))
 }
 
 /// JSON: {"term": "definition", ...}
@@ -97,6 +94,7 @@ pub fn deck_from_json_map(raw: &str) -> anyhow::Result<Deck> {
                 id: next_id,
                 term: term.to_string(),
                 definition: def.to_string(),
+                ..Default::default()
             });
             next_id += 1;
         }
@@ -106,11 +104,7 @@ pub fn deck_from_json_map(raw: &str) -> anyhow::Result<Deck> {
         anyhow::bail!("JSON map had no string definitions");
     }
 
-    Ok(Deck {
-        name: "JSON Dictionary Deck".to_string(),
-        description: None,
-        cards,
-    })
+    Ok(Deck::new("JSON Dictionary Deck", None, cards))
 }
 
 /// JSON: ["word1", "word2", "word3"]
@@ -127,6 +121,7 @@ pub fn deck_from_json_string_array(raw: &str) -> anyhow::Result<Deck> {
                 id: next_id,
                 term: term.to_string(),
                 definition: "?".to_string(),
+                ..Default::default()
             });
             next_id += 1;
         }
@@ -136,11 +131,7 @@ pub fn deck_from_json_string_array(raw: &str) -> anyhow::Result<Deck> {
         anyhow::bail!("string array contained no strings");
     }
 
-    Ok(Deck {
-        name: "Term List Deck".to_string(),
-        description: None,
-        cards,
-    })
+    Ok(Deck::new("Term List Deck", None, cards))
 }
 
 /// JSON: [["term", "definition"], ...]
@@ -159,6 +150,7 @@ pub fn deck_from_json_pairs(raw: &str) -> anyhow::Result<Deck> {
                         id: next_id,
                         term: t.to_string(),
                         definition: d.to_string(),
+                        ..Default::default()
                     });
                     next_id += 1;
                     continue;
@@ -171,11 +163,7 @@ pub fn deck_from_json_pairs(raw: &str) -> anyhow::Result<Deck> {
         anyhow::bail!("no valid term/definition pairs");
     }
 
-    Ok(Deck {
-        name: "JSON Pairs Deck".to_string(),
-        description: None,
-        cards,
-    })
+    Ok(Deck::new("JSON Pairs Deck", None, cards))
 }
 
 /// JSON: { "Category": [["term","def"], ...], ... }
@@ -196,6 +184,7 @@ pub fn deck_from_json_category_map(raw: &str) -> anyhow::Result<Deck> {
                                 id: next_id,
                                 term: t.to_string(),
                                 definition: d.to_string(),
+                                ..Default::default()
                             });
                             next_id += 1;
                         }
@@ -209,9 +198,5 @@ pub fn deck_from_json_category_map(raw: &str) -> anyhow::Result<Deck> {
         anyhow::bail!("no cards in category map");
     }
 
-    Ok(Deck {
-        name: "JSON Category Deck".to_string(),
-        description: None,
-        cards,
-    })
+    Ok(Deck::new("JSON Category Deck", None, cards))
 }
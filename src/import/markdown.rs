@@ -1,8 +1,34 @@
 use crate::model::{Card, Deck};
+use crate::srs::cloze;
 use anyhow::Context;
 
 /// Try multiple Markdown formats and return the first one that works.
+///
+/// Lines with cloze deletions (`{{c1::...}}`, optionally as list items)
+/// are cloze notes whatever format the rest of the file uses; they are
+/// added after the other cards, one card per deletion.
 pub fn deck_from_markdown(raw: &str) -> anyhow::Result<Deck> {
+    let (cloze_lines, rest): (Vec<&str>, Vec<&str>) =
+        raw.lines().partition(|line| cloze::is_cloze(line));
+    if cloze_lines.is_empty() {
+        return deck_from_markdown_pairs(raw);
+    }
+
+    let mut deck = deck_from_markdown_pairs(&rest.join("\n"))
+        .unwrap_or_else(|_| Deck::new("Markdown Deck (Cloze)", None, Vec::new()));
+    for line in cloze_lines {
+        let note = line
+            .trim()
+            .trim_start_matches("- ")
+            .trim_start_matches("* ")
+            .trim();
+        let first_id = deck.cards.len() as u64 + 1;
+        deck.cards.extend(cloze::cards(note, first_id..));
+    }
+    Ok(deck)
+}
+
+fn deck_from_markdown_pairs(raw: &str) -> anyhow::Result<Deck> {
     // Try all formats in order of strict → loose
     try_markdown_heading_pairs(raw)
         .or_else(|_| try_markdown_bullets(raw))
@@ -35,6 +61,7 @@ fn try_markdown_heading_pairs(raw: &str) -> anyhow::Result<Deck> {
                     id: cards.len() as u64 + 1,
                     term,
                     definition: "(no definition)".into(),
+                    ..Default::default()
                 });
             }
             let term = line.trim_start_matches('#').trim().to_string();
@@ -46,6 +73,7 @@ fn try_markdown_heading_pairs(raw: &str) -> anyhow::Result<Deck> {
                     id: cards.len() as u64 + 1,
                     term,
                     definition: line.into(),
+                    ..Default::default()
                 });
             }
         }
@@ -55,11 +83,7 @@ fn try_markdown_heading_pairs(raw: &str) -> anyhow::Result<Deck> {
         anyhow::bail!("not heading-pairs markdown");
     }
 
-    Ok(Deck {
-        name: "Markdown Deck (Headings)".into(),
-        description: None,
-        cards,
-    })
+    Ok(Deck::new("Markdown Deck (Headings)", None, cards))
 }
 
 //
@@ -87,6 +111,7 @@ fn try_markdown_bullets(raw: &str) -> anyhow::Result<Deck> {
                 id: cards.len() as u64 + 1,
                 term: term.trim().into(),
                 definition: def.trim().into(),
+                ..Default::default()
             });
         }
     }
@@ -95,11 +120,7 @@ fn try_markdown_bullets(raw: &str) -> anyhow::Result<Deck> {
         anyhow::bail!("not bullet-style markdown");
     }
 
-    Ok(Deck {
-        name: "Markdown Deck (Bullets)".into(),
-        description: None,
-        cards,
-    })
+    Ok(Deck::new("Markdown Deck (Bullets)", None, cards))
 }
 
 //
@@ -135,6 +156,7 @@ fn try_markdown_table_2col(raw: &str) -> anyhow::Result<Deck> {
             id: cards.len() as u64 + 1,
             term: cols[0].into(),
             definition: cols[1].into(),
+            ..Default::default()
         });
     }
 
@@ -142,11 +164,7 @@ fn try_markdown_table_2col(raw: &str) -> anyhow::Result<Deck> {
         anyhow::bail!("not markdown table");
     }
 
-    Ok(Deck {
-        name: "Markdown Deck (Table)".into(),
-        description: None,
-        cards,
-    })
+    Ok(Deck::new("Markdown Deck (Table)", None, cards))
 }
 
 //
@@ -179,6 +197,7 @@ fn try_markdown_flashcard_blocks(raw: &str) -> anyhow::Result<Deck> {
                     id: cards.len() as u64 + 1,
                     term: term.clone(),
                     definition: def.clone(),
+                    ..Default::default()
                 });
             }
             in_block = false;
@@ -200,11 +219,7 @@ fn try_markdown_flashcard_blocks(raw: &str) -> anyhow::Result<Deck> {
         anyhow::bail!("not code-fenced markdown cards");
     }
 
-    Ok(Deck {
-        name: "Markdown Deck (Card Blocks)".into(),
-        description: None,
-        cards,
-    })
+    Ok(Deck::new("Markdown Deck (Card Blocks)", None, cards))
 }
 
 //
@@ -232,6 +247,7 @@ fn try_markdown_glossary_style(raw: &str) -> anyhow::Result<Deck> {
                 id: cards.len() as u64 + 1,
                 term: term.into(),
                 definition: def.into(),
+                ..Default::default()
             });
         }
     }
@@ -240,11 +256,7 @@ fn try_markdown_glossary_style(raw: &str) -> anyhow::Result<Deck> {
         anyhow::bail!("not glossary markdown");
     }
 
-    Ok(Deck {
-        name: "Markdown Deck (Glossary)".into(),
-        description: None,
-        cards,
-    })
+    Ok(Deck::new("Markdown Deck (Glossary)", None, cards))
 }
 
 //
@@ -267,6 +279,7 @@ fn try_markdown_term_colon_def(raw: &str) -> anyhow::Result<Deck> {
                 id: cards.len() as u64 + 1,
                 term: term.trim().into(),
                 definition: def.trim().into(),
+                ..Default::default()
             });
         }
     }
@@ -275,9 +288,5 @@ fn try_markdown_term_colon_def(raw: &str) -> anyhow::Result<Deck> {
         anyhow::bail!("not colon-definition markdown");
     }
 
-    Ok(Deck {
-        name: "Markdown Deck (Colon Format)".into(),
-        description: None,
-        cards,
-    })
+    Ok(Deck::new("Markdown Deck (Colon Format)", None, cards))
 }
@@ -39,15 +39,12 @@ pub fn deck_from_txt(name: &str, description: Option<String>, raw: &str) -> Deck
             id: next_id,
             term: term.to_string(),
             definition: definition.to_string(),
+            ..Default::default()
         });
         next_id += 1;
     }
 
-    Deck {
-        name: name.to_string(),
-        description,
-        cards,
-    }
+    Deck::new(name, description, cards)
 }
 
 /// Try a bunch of separators that people commonly use between term & definition.
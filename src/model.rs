@@ -3,18 +3,56 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 
+use crate::srs::{cloze, DeckSrsSettings};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Deck {
     pub name: String,
     pub description: Option<String>,
     pub cards: Vec<Card>,
+
+    /// Optional per-deck scheduling overrides (`"srs": { ... }` in JSON).
+    #[serde(default, skip_serializing_if = "DeckSrsSettings::is_empty")]
+    pub srs: DeckSrsSettings,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Card {
     pub id: u64,
     pub term: String,
     pub definition: String,
+
+    /// Language of `term` (e.g. `"fr"`), when the deck format records it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub term_lang: Option<String>,
+
+    /// Language of `definition`, likewise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub def_lang: Option<String>,
+
+    /// Deck-side tags (topics, difficulty, etc.).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Cloze cards: which `{{cN::...}}` deletion of the note text in
+    /// `definition` this card asks; `term` holds its answer (see
+    /// `srs::cloze`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cloze: Option<u32>,
+
+    /// Image or audio file shown / played with the card, relative to the
+    /// deck file (see `gui::media`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media: Option<String>,
+
+    /// Extra detail shown on the study card (see `StudyOptions::details`):
+    /// example sentences, free-form notes and a link to read more.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hyperlink: Option<String>,
 }
 
 impl Card {
@@ -25,6 +63,126 @@ impl Card {
             id,
             term: term.into(),
             definition: definition.into(),
+            ..Default::default()
+        }
+    }
+
+    /// The directions this card is asked in when `studied` are chosen.
+    /// A cloze card only works one way round (blanked note → answer).
+    pub fn directions<'a>(&self, studied: &'a [Direction]) -> &'a [Direction] {
+        if self.cloze.is_some() {
+            &[Direction::Reverse]
+        } else {
+            studied
+        }
+    }
+
+    /// Whether the card has examples, notes or a hyperlink to show.
+    pub fn has_details(&self) -> bool {
+        !self.examples.is_empty() || self.notes.is_some() || self.hyperlink.is_some()
+    }
+}
+
+/// Which side of a card is the prompt.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    /// Term shown, definition asked (term → definition).
+    Forward,
+    /// Definition shown, term asked (definition → term): the only
+    /// direction before directions could be chosen.
+    #[default]
+    Reverse,
+}
+
+/// Set on the review keys of the forward direction. The reverse direction
+/// keeps the bare card id, so progress saved before directions existed
+/// stays with the direction it was earned in. Card ids count up from 1,
+/// far below this.
+const FORWARD_KEY_BIT: u64 = 1 << 32;
+
+impl Direction {
+    pub const ALL: [Direction; 2] = [Direction::Forward, Direction::Reverse];
+
+    /// Key of this direction of `card_id` in the progress map (and in the
+    /// session queue): each direction is scheduled on its own.
+    pub fn key(self, card_id: u64) -> u64 {
+        match self {
+            Direction::Forward => card_id | FORWARD_KEY_BIT,
+            Direction::Reverse => card_id,
+        }
+    }
+
+    /// Inverse of `key`: the card id and direction a review key stands for.
+    pub fn split_key(key: u64) -> (u64, Direction) {
+        if key & FORWARD_KEY_BIT != 0 {
+            (key & !FORWARD_KEY_BIT, Direction::Forward)
+        } else {
+            (key, Direction::Reverse)
+        }
+    }
+
+    /// `card` as asked in this direction: the prompt is always the
+    /// `definition` and the answer the `term`, so the forward direction
+    /// swaps them.
+    pub fn orient(self, card: &Card) -> Card {
+        match self {
+            Direction::Reverse => card.clone(),
+            Direction::Forward => Card {
+                term: card.definition.clone(),
+                definition: card.term.clone(),
+                term_lang: card.def_lang.clone(),
+                def_lang: card.term_lang.clone(),
+                ..card.clone()
+            },
+        }
+    }
+}
+
+/// Where a card is in its life cycle (see `srs::steps`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CardPhase {
+    /// Never answered.
+    #[default]
+    New,
+    /// Going through the short learning steps after its first answer.
+    Learning,
+    /// Graduated: scheduled in days by the active scheduler.
+    Review,
+    /// Lapsed during review; going through the relearning steps.
+    Relearning,
+}
+
+/// A colour the learner can mark a card with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CardFlag {
+    Red,
+    Orange,
+    Green,
+    Blue,
+    Purple,
+}
+
+impl CardFlag {
+    pub const ALL: [CardFlag; 5] = [
+        CardFlag::Red,
+        CardFlag::Orange,
+        CardFlag::Green,
+        CardFlag::Blue,
+        CardFlag::Purple,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CardFlag::Red => "Red",
+            CardFlag::Orange => "Orange",
+            CardFlag::Green => "Green",
+            CardFlag::Blue => "Blue",
+            CardFlag::Purple => "Purple",
         }
     }
 }
@@ -36,8 +194,59 @@ pub struct ReviewState {
     pub ease_factor: f64,
     pub repetitions: u32,
     pub next_review: DateTime<Utc>,
+
+    /// When the card was last answered (`None` for cards never reviewed,
+    /// or progress saved before this was tracked).
+    #[serde(default)]
+    pub last_review: Option<DateTime<Utc>>,
+
+    /// FSRS memory stability in days (`None` until FSRS has seen the card).
+    #[serde(default)]
+    pub stability: Option<f64>,
+
+    /// FSRS difficulty, 1.0 ..= 10.0 (`None` until FSRS has seen the card).
+    #[serde(default)]
+    pub difficulty: Option<f64>,
+
+    /// Learning phase. Progress saved before phases existed loads as `New`
+    /// and is corrected by `srs::progress::reconcile`.
+    #[serde(default)]
+    pub phase: CardPhase,
+
+    /// Index into the learning / relearning steps while in those phases.
+    #[serde(default)]
+    pub step: u32,
+
+    /// How many times the card has been forgotten after graduating.
+    #[serde(default)]
+    pub lapses: u32,
+
+    /// Suspended cards are never put into a study session.
+    #[serde(default)]
+    pub suspended: bool,
+
+    /// Buried cards sit out until this time (the start of the next study day).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buried_until: Option<DateTime<Utc>>,
+
+    /// Colour the learner flagged the card with, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flag: Option<CardFlag>,
+
+    /// Learner-side tags (e.g. `"leech"`); kept with the progress rather
+    /// than in the deck file.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Wrong answers the learner picked for this card, most recent last
+    /// (at most `MAX_CONFUSED`). Used to pick harder distractors.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub confused_with: Vec<String>,
 }
 
+/// How many confused terms a `ReviewState` remembers.
+pub const MAX_CONFUSED: usize = 8;
+
 impl ReviewState {
     pub fn new(card_id: u64, now: DateTime<Utc>) -> Self {
         Self {
@@ -46,14 +255,67 @@ impl ReviewState {
             ease_factor: 2.5,
             repetitions: 0,
             next_review: now,
+            last_review: None,
+            stability: None,
+            difficulty: None,
+            phase: CardPhase::New,
+            step: 0,
+            lapses: 0,
+            suspended: false,
+            buried_until: None,
+            flag: None,
+            tags: Vec::new(),
+            confused_with: Vec::new(),
+        }
+    }
+
+    /// A card counts as new until it has been answered at least once.
+    pub fn is_new(&self) -> bool {
+        self.phase == CardPhase::New && self.last_review.is_none() && self.repetitions == 0
+    }
+
+    /// Whether the card is in its intra-day learning or relearning steps.
+    pub fn is_learning(&self) -> bool {
+        matches!(self.phase, CardPhase::Learning | CardPhase::Relearning)
+    }
+
+    /// Whether the card is buried at `now`.
+    pub fn is_buried(&self, now: DateTime<Utc>) -> bool {
+        self.buried_until.is_some_and(|until| until > now)
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Remember that `term` was picked instead of this card's answer.
+    pub fn record_confusion(&mut self, term: &str) {
+        self.confused_with.retain(|t| t != term);
+        self.confused_with.push(term.to_string());
+        if self.confused_with.len() > MAX_CONFUSED {
+            self.confused_with.remove(0);
         }
     }
 }
 
 impl Deck {
+    /// Convenience constructor for importers: a deck with no per-deck
+    /// scheduling overrides.
+    pub fn new(name: impl Into<String>, description: Option<String>, cards: Vec<Card>) -> Self {
+        Self {
+            name: name.into(),
+            description,
+            cards,
+            srs: DeckSrsSettings::default(),
+        }
+    }
+
+    /// Load a deck file. Cloze notes written into it by hand are expanded
+    /// into their cards (see `cloze::expand_notes`).
     pub fn from_json_file<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
         let data = fs::read_to_string(path)?;
-        let deck: Deck = serde_json::from_str(&data)?;
+        let mut deck: Deck = serde_json::from_str(&data)?;
+        cloze::expand_notes(&mut deck.cards);
         Ok(deck)
     }
 
@@ -0,0 +1,271 @@
+// src/srs/choice.rs
+//
+// Multiple-choice option building: the correct card plus distractors
+// drawn from the rest of the deck, in shuffled order.
+//
+// Distractors are either sampled at random or ranked by how easily they
+// could be mistaken for the answer (`DistractorStrategy::Similar`):
+// terms the learner has picked by mistake before, shared tags or
+// language, the same script, similar length and spelling.
+
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::model::Card;
+
+use super::text::{self, Script};
+
+/// Fewest answer buttons a multiple-choice card can have.
+pub const MIN_OPTIONS: usize = 2;
+
+/// Most answer buttons a multiple-choice card can have.
+pub const MAX_OPTIONS: usize = 8;
+
+// Weights for `similarity_score`. A past confusion outranks everything
+// else; the random jitter only breaks ties between close candidates.
+const CONFUSED_WEIGHT: f64 = 4.0;
+const SHARED_TAG_WEIGHT: f64 = 2.0;
+const SAME_LANG_WEIGHT: f64 = 1.0;
+const SAME_SCRIPT_WEIGHT: f64 = 1.0;
+const LENGTH_WEIGHT: f64 = 1.0;
+const SPELLING_WEIGHT: f64 = 2.0;
+const JITTER: f64 = 0.75;
+
+/// How distractors are picked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DistractorStrategy {
+    /// Uniformly at random from the deck.
+    #[default]
+    Random,
+    /// The cards most easily mistaken for the answer.
+    Similar,
+}
+
+impl DistractorStrategy {
+    pub const ALL: [DistractorStrategy; 2] =
+        [DistractorStrategy::Random, DistractorStrategy::Similar];
+
+    /// Human-readable name for options screens.
+    pub fn label(self) -> &'static str {
+        match self {
+            DistractorStrategy::Random => "Random",
+            DistractorStrategy::Similar => "Similar (harder)",
+        }
+    }
+}
+
+/// Builds the answer options for a multiple-choice card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChoiceBuilder {
+    /// Number of options including the correct one
+    /// (`MIN_OPTIONS..=MAX_OPTIONS`; fewer if the deck is small).
+    pub option_count: usize,
+    pub strategy: DistractorStrategy,
+}
+
+impl Default for ChoiceBuilder {
+    fn default() -> Self {
+        Self {
+            option_count: 4,
+            strategy: DistractorStrategy::default(),
+        }
+    }
+}
+
+impl ChoiceBuilder {
+    /// The correct card plus distractors picked by `strategy`, shuffled.
+    ///
+    /// `confused` lists terms the learner has wrongly picked for this card
+    /// before (see `ReviewState::confused_with`); only the `Similar`
+    /// strategy uses it.
+    ///
+    /// Answers are matched by term, so cards sharing the correct card's
+    /// term (or each other's) are never offered twice. Cloze answers and
+    /// plain terms are never mixed.
+    pub fn build(
+        &self,
+        correct: &Card,
+        deck: &[Card],
+        confused: &[String],
+        rng: &mut dyn RngCore,
+    ) -> Vec<Card> {
+        let count = self.option_count.clamp(MIN_OPTIONS, MAX_OPTIONS);
+
+        let mut candidates: Vec<&Card> = deck
+            .iter()
+            .filter(|c| c.id != correct.id && c.term != correct.term)
+            .filter(|c| c.cloze.is_some() == correct.cloze.is_some())
+            .collect();
+        candidates.shuffle(rng);
+
+        if self.strategy == DistractorStrategy::Similar {
+            let script = Script::detect(&correct.term);
+            let mut scored: Vec<(f64, &Card)> = candidates
+                .into_iter()
+                .map(|c| {
+                    let score = similarity_score(correct, script, c, confused);
+                    (score + rng.gen::<f64>() * JITTER, c)
+                })
+                .collect();
+            scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+            candidates = scored.into_iter().map(|(_, c)| c).collect();
+        }
+
+        let mut options = vec![correct.clone()];
+        for card in candidates {
+            if options.len() >= count {
+                break;
+            }
+            if options.iter().all(|o| o.term != card.term) {
+                options.push(card.clone());
+            }
+        }
+
+        options.shuffle(rng);
+        options
+    }
+}
+
+/// How easily `candidate` could be mistaken for `correct` (higher = more).
+fn similarity_score(correct: &Card, script: Script, candidate: &Card, confused: &[String]) -> f64 {
+    let mut score = 0.0;
+
+    if confused.contains(&candidate.term) {
+        score += CONFUSED_WEIGHT;
+    }
+    if candidate.tags.iter().any(|t| correct.tags.contains(t)) {
+        score += SHARED_TAG_WEIGHT;
+    }
+    if candidate.term_lang.is_some() && candidate.term_lang == correct.term_lang {
+        score += SAME_LANG_WEIGHT;
+    }
+    if Script::detect(&candidate.term) == script {
+        score += SAME_SCRIPT_WEIGHT;
+    }
+
+    let (a, b) = (correct.term.chars().count(), candidate.term.chars().count());
+    score += LENGTH_WEIGHT * a.min(b) as f64 / a.max(b).max(1) as f64;
+
+    score += SPELLING_WEIGHT
+        * text::similarity(&correct.term.to_lowercase(), &candidate.term.to_lowercase());
+
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Direction;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn deck(n: u64) -> Vec<Card> {
+        (1..=n)
+            .map(|id| Card::new(id, format!("term {id}"), format!("def {id}")))
+            .collect()
+    }
+
+    #[test]
+    fn options_include_the_answer_once_and_have_unique_terms() {
+        let deck = deck(20);
+        let mut rng = StdRng::seed_from_u64(3);
+        let builder = ChoiceBuilder {
+            option_count: 6,
+            strategy: DistractorStrategy::Random,
+        };
+
+        for card in &deck {
+            let options = builder.build(card, &deck, &[], &mut rng);
+            assert_eq!(options.len(), 6);
+            assert_eq!(options.iter().filter(|o| o.id == card.id).count(), 1);
+
+            let mut terms: Vec<&str> = options.iter().map(|o| o.term.as_str()).collect();
+            terms.sort();
+            terms.dedup();
+            assert_eq!(terms.len(), 6);
+        }
+    }
+
+    #[test]
+    fn answer_position_varies() {
+        let deck = deck(20);
+        let mut rng = StdRng::seed_from_u64(3);
+        let builder = ChoiceBuilder::default();
+
+        let positions: Vec<usize> = (0..20)
+            .map(|_| {
+                let options = builder.build(&deck[0], &deck, &[], &mut rng);
+                options.iter().position(|o| o.id == deck[0].id).unwrap()
+            })
+            .collect();
+        assert!(positions.iter().any(|&p| p != positions[0]));
+    }
+
+    #[test]
+    fn forward_cards_compare_the_language_shown() {
+        let card = |id, def_lang: &str| Card {
+            term_lang: Some("fr".to_string()),
+            def_lang: Some(def_lang.to_string()),
+            ..Card::new(id, "chat", "cat")
+        };
+        let correct = Direction::Forward.orient(&card(1, "en"));
+        let same = Direction::Forward.orient(&card(2, "en"));
+        let other = Direction::Forward.orient(&card(3, "de"));
+
+        let script = Script::detect(&correct.term);
+        let score = |c: &Card| similarity_score(&correct, script, c, &[]);
+        assert_eq!(score(&same) - score(&other), SAME_LANG_WEIGHT);
+    }
+
+    #[test]
+    fn random_is_the_default_strategy() {
+        assert_eq!(
+            ChoiceBuilder::default().strategy,
+            DistractorStrategy::Random
+        );
+    }
+
+    #[test]
+    fn small_decks_give_fewer_options() {
+        let deck = deck(3);
+        let mut rng = StdRng::seed_from_u64(3);
+        let builder = ChoiceBuilder {
+            option_count: 8,
+            ..Default::default()
+        };
+        let options = builder.build(&deck[0], &deck, &[], &mut rng);
+        assert_eq!(options.len(), 3);
+    }
+
+    #[test]
+    fn similar_strategy_prefers_confusable_cards() {
+        let mut deck = vec![
+            Card::new(1, "house", "Haus"),
+            Card::new(2, "horse", "Pferd"),
+            Card::new(3, "mouse", "Maus"),
+            Card::new(4, "собака", "Hund"),
+            Card::new(5, "encyclopaedia", "Lexikon"),
+            Card::new(6, "犬", "Hund"),
+            Card::new(7, "tree", "Baum"),
+        ];
+        deck[0].tags = vec!["animals".into()];
+        deck[6].tags = vec!["animals".into()];
+
+        let builder = ChoiceBuilder {
+            strategy: DistractorStrategy::Similar,
+            ..Default::default()
+        };
+        let confused = vec!["encyclopaedia".to_string()];
+        for seed in 0..10 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let options = builder.build(&deck[0], &deck, &confused, &mut rng);
+            let ids: Vec<u64> = options.iter().map(|o| o.id).collect();
+            // The past confusion and the tag-mate always make it; the other
+            // scripts never do.
+            assert!(ids.contains(&5) && ids.contains(&7));
+            assert!(!ids.contains(&4) && !ids.contains(&6));
+        }
+    }
+}
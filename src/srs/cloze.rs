@@ -0,0 +1,375 @@
+// src/srs/cloze.rs
+//
+// Cloze deletions, Anki style: `{{c1::answer}}` or `{{c1::answer::hint}}`
+// inside a sentence.
+//
+// A cloze note becomes one card per deletion index. Each card keeps the
+// whole note text in `definition`, its index in `Card::cloze` and the
+// answer in `term`, so every study mode can ask it like any other card:
+// the prompt is the note with that index blanked out and the others
+// filled in.
+
+use crate::model::Card;
+
+use super::mflash::card_ids;
+
+/// Shown in place of an asked deletion that has no hint.
+pub const BLANK: &str = "[...]";
+
+/// Set on the id of a note's card whose own id (the note's id plus its
+/// position) belongs to another card; the rest of the id is the note's id
+/// and the position, so it doesn't depend on the rest of the deck. Card
+/// ids count up from 1, far below this (and below `Direction::key`'s bit).
+const DISPLACED_ID_BIT: u64 = 1 << 31;
+
+/// Bits of a displaced id holding the card's position in its note.
+const POSITION_BITS: u32 = 8;
+
+/// One piece of a cloze note.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Segment<'a> {
+    Text(&'a str),
+    Deletion {
+        index: u32,
+        answer: &'a str,
+        hint: Option<&'a str>,
+    },
+}
+
+/// Split `text` into plain text and deletions. Malformed markers are kept
+/// as plain text.
+pub fn parse(text: &str) -> Vec<Segment<'_>> {
+    let mut out = Vec::new();
+    let mut plain_start = 0;
+    let mut pos = 0;
+
+    while let Some(rel) = text[pos..].find("{{c") {
+        let start = pos + rel;
+        match parse_deletion(&text[start..]) {
+            Some((deletion, len)) => {
+                if plain_start < start {
+                    out.push(Segment::Text(&text[plain_start..start]));
+                }
+                out.push(deletion);
+                pos = start + len;
+                plain_start = pos;
+            }
+            None => pos = start + "{{c".len(),
+        }
+    }
+
+    if plain_start < text.len() {
+        out.push(Segment::Text(&text[plain_start..]));
+    }
+    out
+}
+
+/// A deletion at the start of `s` and its length in bytes.
+fn parse_deletion(s: &str) -> Option<(Segment<'_>, usize)> {
+    let rest = s.strip_prefix("{{c")?;
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let index: u32 = rest[..digits].parse().ok()?;
+    let body_start = rest[digits..].strip_prefix("::")?;
+    let end = body_start.find("}}")?;
+    let body = &body_start[..end];
+
+    let (answer, hint) = match body.split_once("::") {
+        Some((answer, hint)) => (answer, Some(hint)),
+        None => (body, None),
+    };
+    let len = s.len() - body_start.len() + end + "}}".len();
+    Some((
+        Segment::Deletion {
+            index,
+            answer,
+            hint,
+        },
+        len,
+    ))
+}
+
+/// Deletion indices used in `text`, ascending and without duplicates.
+pub fn indices(text: &str) -> Vec<u32> {
+    let mut out: Vec<u32> = parse(text)
+        .into_iter()
+        .filter_map(|s| match s {
+            Segment::Deletion { index, .. } => Some(index),
+            Segment::Text(_) => None,
+        })
+        .collect();
+    out.sort_unstable();
+    out.dedup();
+    out
+}
+
+/// Whether `text` has at least one cloze deletion.
+pub fn is_cloze(text: &str) -> bool {
+    !indices(text).is_empty()
+}
+
+/// The answer to deletion `index` (several deletions sharing an index are
+/// joined with ", ").
+pub fn answer(text: &str, index: u32) -> String {
+    let answers: Vec<&str> = parse(text)
+        .into_iter()
+        .filter_map(|s| match s {
+            Segment::Deletion {
+                index: i, answer, ..
+            } if i == index => Some(answer),
+            _ => None,
+        })
+        .collect();
+    answers.join(", ")
+}
+
+/// `text` as asked for deletion `index`: that deletion is blanked (showing
+/// its hint, if any) and every other deletion is filled in.
+pub fn prompt(text: &str, index: u32) -> String {
+    parse(text)
+        .into_iter()
+        .map(|s| match s {
+            Segment::Text(t) => t.to_string(),
+            Segment::Deletion { index: i, hint, .. } if i == index => blank(hint),
+            Segment::Deletion { answer, .. } => answer.to_string(),
+        })
+        .collect()
+}
+
+/// What an asked deletion shows instead of its answer: the hint in
+/// brackets, or `BLANK`.
+pub fn blank(hint: Option<&str>) -> String {
+    hint.map_or_else(|| BLANK.to_string(), |hint| format!("[{hint}]"))
+}
+
+/// `text` with every deletion filled in.
+pub fn reveal(text: &str) -> String {
+    parse(text)
+        .into_iter()
+        .map(|s| match s {
+            Segment::Text(t) => t,
+            Segment::Deletion { answer, .. } => answer,
+        })
+        .collect()
+}
+
+/// One card per deletion index of the note `text`, taking their ids from
+/// `ids` in order (`first_id..` to count up from `first_id`).
+pub fn cards(text: &str, ids: impl IntoIterator<Item = u64>) -> Vec<Card> {
+    indices(text)
+        .into_iter()
+        .zip(ids)
+        .map(|(index, id)| Card {
+            id,
+            term: answer(text, index),
+            definition: text.to_string(),
+            cloze: Some(index),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Id for card `position` (0 = first) of the note whose first card is
+/// `note_id`, when `note_id + position` is taken (see `DISPLACED_ID_BIT`).
+/// `None` if the note id or position doesn't fit.
+pub fn displaced_id(note_id: u64, position: u64) -> Option<u64> {
+    (note_id < DISPLACED_ID_BIT >> POSITION_BITS && position < 1 << POSITION_BITS)
+        .then_some(DISPLACED_ID_BIT | note_id << POSITION_BITS | position)
+}
+
+/// Whether `id` was made by `displaced_id`.
+pub fn is_displaced(id: u64) -> bool {
+    id & DISPLACED_ID_BIT != 0 && id < DISPLACED_ID_BIT << 1
+}
+
+/// Expand cloze notes written straight into a deck file (cloze text on
+/// either side of a card without a `cloze` index) into one card per
+/// deletion, keeping the note's tags, media and details. Ids are given as
+/// for `.mflash` decks (see `mflash::card_ids`): the note's first card
+/// keeps its id, the others take the ids after it, and any of those
+/// already used by other cards get ids made from the note's own id
+/// (`displaced_id`), which stay put as cards are added or removed.
+/// Cards already expanded are left alone.
+pub fn expand_notes(cards: &mut Vec<Card>) {
+    let notes: Vec<Option<String>> = cards
+        .iter()
+        .map(|card| {
+            [&card.definition, &card.term]
+                .into_iter()
+                .find(|text| is_cloze(text))
+                .filter(|_| card.cloze.is_none())
+                .cloned()
+        })
+        .collect();
+    let ids = card_ids(cards.iter().zip(&notes).map(|(card, note)| {
+        let count = note.as_deref().map_or(1, |note| indices(note).len() as u64);
+        (Some(card.id), count)
+    }));
+
+    let mut out = Vec::with_capacity(cards.len());
+    for ((card, note), ids) in std::mem::take(cards).into_iter().zip(notes).zip(ids) {
+        let Some(note) = note else {
+            out.push(Card { id: ids[0], ..card });
+            continue;
+        };
+        out.extend(self::cards(&note, ids).into_iter().map(|deletion| Card {
+            id: deletion.id,
+            term: deletion.term,
+            definition: deletion.definition,
+            cloze: deletion.cloze,
+            ..card.clone()
+        }));
+    }
+    *cards = out;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Deck;
+
+    const NOTE: &str = "{{c1::Paris}} is the capital of {{c2::France::country}}.";
+
+    #[test]
+    fn parses_answers_and_hints() {
+        assert_eq!(
+            parse(NOTE),
+            [
+                Segment::Deletion {
+                    index: 1,
+                    answer: "Paris",
+                    hint: None
+                },
+                Segment::Text(" is the capital of "),
+                Segment::Deletion {
+                    index: 2,
+                    answer: "France",
+                    hint: Some("country")
+                },
+                Segment::Text("."),
+            ]
+        );
+        assert_eq!(reveal(NOTE), "Paris is the capital of France.");
+    }
+
+    #[test]
+    fn malformed_markers_stay_plain_text() {
+        assert!(!is_cloze("{{c::x}} and {{cx::y}} and {{c1::open"));
+        assert_eq!(reveal("a {{c}} b {{c2::ok}}"), "a {{c}} b ok");
+    }
+
+    #[test]
+    fn prompt_blanks_only_the_asked_index() {
+        assert_eq!(prompt(NOTE, 1), "[...] is the capital of France.");
+        assert_eq!(prompt(NOTE, 2), "Paris is the capital of [country].");
+    }
+
+    #[test]
+    fn one_card_per_index() {
+        let note = "{{c1::H}}{{c2::2}}{{c1::O}}";
+        let cards = cards(note, 5..);
+        assert_eq!(cards.len(), 2);
+        assert_eq!((cards[0].id, cards[0].cloze), (5, Some(1)));
+        assert_eq!(cards[0].term, "H, O");
+        assert_eq!((cards[1].id, cards[1].cloze), (6, Some(2)));
+        assert_eq!(cards[1].term, "2");
+        assert!(cards.iter().all(|c| c.definition == note));
+    }
+
+    #[test]
+    fn notes_in_deck_files_are_expanded_once() {
+        let mut deck = vec![
+            Card::new(1, "", NOTE),
+            Card::new(3, "dog", "a barking pet"),
+            Card {
+                tags: vec!["geo".to_string()],
+                ..Card::new(4, "{{c1::Rome}} is in {{c2::Italy}}", "")
+            },
+        ];
+        expand_notes(&mut deck);
+
+        let ids: Vec<(u64, Option<u32>)> = deck.iter().map(|c| (c.id, c.cloze)).collect();
+        assert_eq!(
+            ids,
+            [
+                (1, Some(1)),
+                (2, Some(2)),
+                (3, None),
+                (4, Some(1)),
+                (5, Some(2))
+            ]
+        );
+        assert_eq!(deck[1].term, "France");
+        assert_eq!(deck[4].definition, "{{c1::Rome}} is in {{c2::Italy}}");
+        assert_eq!(deck[4].tags, ["geo"]);
+
+        let expanded = deck.clone();
+        expand_notes(&mut deck);
+        assert_eq!(deck.len(), expanded.len());
+        assert!(deck.iter().zip(&expanded).all(|(a, b)| a.id == b.id));
+    }
+
+    #[test]
+    fn note_whose_ids_are_taken_keeps_the_other_cards_ids() {
+        let mut deck = vec![
+            Card::new(1, "", NOTE),
+            Card::new(2, "dog", "a barking pet"),
+            Card::new(3, "cat", "a purring pet"),
+        ];
+        expand_notes(&mut deck);
+
+        let ids: Vec<(u64, Option<u32>)> = deck.iter().map(|c| (c.id, c.cloze)).collect();
+        let displaced = displaced_id(1, 1).unwrap();
+        assert_eq!(
+            ids,
+            [(1, Some(1)), (displaced, Some(2)), (2, None), (3, None)]
+        );
+    }
+
+    #[test]
+    fn taken_deletion_keeps_its_id_when_a_note_is_added() {
+        let mut deck = vec![Card::new(1, "", NOTE), Card::new(2, "dog", "a barking pet")];
+        expand_notes(&mut deck);
+        let before = deck[1].id;
+
+        // Another hand-written note, with ids above and below the first.
+        let mut deck = vec![
+            Card::new(1, "", NOTE),
+            Card::new(2, "dog", "a barking pet"),
+            Card::new(3, "", "{{c1::Rome}} is in {{c2::Italy}}"),
+        ];
+        expand_notes(&mut deck);
+        assert_eq!(deck[1].id, before);
+        assert_eq!(deck[4].id, 4);
+
+        let mut deck = vec![
+            Card::new(9, "", "{{c1::Rome}} is in {{c2::Italy}}"),
+            Card::new(1, "", NOTE),
+            Card::new(2, "dog", "a barking pet"),
+        ];
+        expand_notes(&mut deck);
+        assert_eq!(deck[3].id, before);
+    }
+
+    #[test]
+    fn note_ids_survive_adding_cards_to_the_deck_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capitals.json");
+        let mut deck = Deck::new("Capitals", None, vec![Card::new(5, "", NOTE)]);
+        deck.to_json_file(&path).unwrap();
+
+        let ids = |path: &std::path::Path| -> Vec<(u64, Option<u32>)> {
+            let deck = Deck::from_json_file(path).unwrap();
+            deck.cards.iter().map(|c| (c.id, c.cloze)).collect()
+        };
+        assert_eq!(ids(&path), [(5, Some(1)), (6, Some(2))]);
+
+        // Cards added before and after the note, with higher ids too.
+        deck.cards.insert(0, Card::new(9, "dog", "a barking pet"));
+        deck.cards.push(Card::new(7, "cat", "a purring pet"));
+        deck.to_json_file(&path).unwrap();
+        assert_eq!(
+            ids(&path),
+            [(9, None), (5, Some(1)), (6, Some(2)), (7, None)]
+        );
+    }
+}
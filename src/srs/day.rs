@@ -0,0 +1,150 @@
+// src/srs/day.rs
+//
+// Study-day boundaries in the user's local time zone.
+//
+// A "study day" starts at a configurable rollover hour rather than at
+// midnight, so a late-night session still counts towards the day it
+// started on (with a 04:00 rollover, 01:30 on Tuesday is still Monday).
+
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc};
+
+/// Default hour (local time) at which a new study day begins.
+pub const DEFAULT_ROLLOVER_HOUR: u32 = 4;
+
+/// The study day that `now` falls in.
+pub fn study_day(now: DateTime<Utc>, rollover_hour: u32) -> NaiveDate {
+    study_day_in(&Local, now, rollover_hour)
+}
+
+/// When the study day after `now`'s begins, as a UTC instant.
+pub fn next_day_start(now: DateTime<Utc>, rollover_hour: u32) -> DateTime<Utc> {
+    next_day_start_in(&Local, now, rollover_hour)
+}
+
+fn study_day_in<Tz: TimeZone>(tz: &Tz, now: DateTime<Utc>, rollover_hour: u32) -> NaiveDate {
+    let shifted = now.with_timezone(tz) - Duration::hours(i64::from(rollover_hour.min(23)));
+    shifted.date_naive()
+}
+
+fn next_day_start_in<Tz: TimeZone>(
+    tz: &Tz,
+    now: DateTime<Utc>,
+    rollover_hour: u32,
+) -> DateTime<Utc> {
+    let Some(start) = study_day_in(tz, now, rollover_hour)
+        .succ_opt()
+        .and_then(|d| d.and_hms_opt(rollover_hour.min(23), 0, 0))
+    else {
+        // Only reachable at the end of chrono's calendar.
+        return now + Duration::days(1);
+    };
+
+    // Clocks going forward can skip the rollover time (02:00 on a spring
+    // night); the day then starts when they resume. Every zone's changes
+    // fall on a quarter hour, so the first quarter hour that exists is it.
+    (0..24 * 4)
+        .map(|quarter| start + Duration::minutes(15 * quarter))
+        .find_map(|local| tz.from_local_datetime(&local).earliest())
+        .map(|t| t.with_timezone(&Utc))
+        .unwrap_or(now + Duration::days(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, MappedLocalTime, NaiveDateTime};
+
+    /// Central European time around the 2025 spring change: UTC+1 until
+    /// 2025-03-30 01:00 UTC, UTC+2 after, so 02:00..03:00 local that night
+    /// never happens.
+    #[derive(Debug, Clone, Copy)]
+    struct SpringForward;
+
+    fn change() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 3, 30)
+            .unwrap()
+            .and_hms_opt(1, 0, 0)
+            .unwrap()
+    }
+
+    fn offset(hours: i32) -> FixedOffset {
+        FixedOffset::east_opt(hours * 3600).unwrap()
+    }
+
+    impl TimeZone for SpringForward {
+        type Offset = FixedOffset;
+
+        fn from_offset(_: &FixedOffset) -> Self {
+            SpringForward
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_local_datetime(
+            &self,
+            local: &NaiveDateTime,
+        ) -> MappedLocalTime<FixedOffset> {
+            let winter = *local - Duration::hours(1) < change();
+            let summer = *local - Duration::hours(2) >= change();
+            match (winter, summer) {
+                (true, false) => MappedLocalTime::Single(offset(1)),
+                (false, true) => MappedLocalTime::Single(offset(2)),
+                (true, true) => MappedLocalTime::Ambiguous(offset(1), offset(2)),
+                (false, false) => MappedLocalTime::None,
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            self.offset_from_utc_datetime(&utc.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            offset(if *utc < change() { 1 } else { 2 })
+        }
+    }
+
+    fn utc(d: u32, h: u32, m: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, d, h, m, 0).unwrap()
+    }
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, d).unwrap()
+    }
+
+    #[test]
+    fn study_day_turns_over_at_the_rollover_hour() {
+        let tz = &SpringForward;
+        // 03:59 and 04:00 local (UTC+1) on the 12th.
+        assert_eq!(study_day_in(tz, utc(12, 2, 59), 4), date(11));
+        assert_eq!(study_day_in(tz, utc(12, 3, 0), 4), date(12));
+        // Late evening is still the same day.
+        assert_eq!(study_day_in(tz, utc(12, 22, 30), 4), date(12));
+        // A midnight rollover is the calendar day.
+        assert_eq!(study_day_in(tz, utc(12, 22, 59), 0), date(12));
+        assert_eq!(study_day_in(tz, utc(12, 23, 0), 0), date(13));
+    }
+
+    #[test]
+    fn next_day_starts_at_the_next_rollover() {
+        let tz = &SpringForward;
+        // 23:30 local on the 12th: the 13th starts at 04:00 local.
+        assert_eq!(next_day_start_in(tz, utc(12, 22, 30), 4), utc(13, 3, 0));
+        // 01:30 local on the 13th still belongs to the 12th.
+        assert_eq!(next_day_start_in(tz, utc(13, 0, 30), 4), utc(13, 3, 0));
+        // Across the change 04:00 local is UTC+2.
+        assert_eq!(next_day_start_in(tz, utc(29, 12, 0), 4), utc(30, 2, 0));
+    }
+
+    #[test]
+    fn next_day_skipped_by_the_clocks_starts_when_they_resume() {
+        let tz = &SpringForward;
+        // 02:00 local on the 30th doesn't exist: the day starts at 03:00
+        // local, the moment of the change.
+        let start = next_day_start_in(tz, utc(29, 12, 0), 2);
+        assert_eq!(start, utc(30, 1, 0));
+        assert_eq!(study_day_in(tz, start, 2), date(30));
+        assert_eq!(study_day_in(tz, start - Duration::seconds(1), 2), date(29));
+    }
+}
@@ -0,0 +1,252 @@
+// src/srs/forecast.rs
+//
+// Workload forecast: how many reviews fall due on each of the next N
+// study days.
+//
+// The forecast replays the deck forward day by day with the real
+// scheduler (previewed, so without interval fuzz), assuming every card is
+// answered "Good". Daily caps apply, so reviews over the cap spill over
+// to the next day and new cards are introduced at most `new_per_day` at a
+// time, just like real sessions. Today only gets what is left of its caps.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::model::ReviewState;
+
+use super::{day, DailyCounts, DailyLimits, Rating, Scheduler};
+
+/// Safety limit on same-day repeats (learning steps) per simulated card.
+const MAX_REPEATS_PER_DAY: usize = 16;
+
+/// Projected workload for one study day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForecastDay {
+    pub date: NaiveDate,
+    /// Review cards due that day (after the daily review cap).
+    pub reviews: usize,
+    /// New cards introduced that day (after the daily new-card cap).
+    pub new_cards: usize,
+}
+
+impl ForecastDay {
+    pub fn total(&self) -> usize {
+        self.reviews + self.new_cards
+    }
+}
+
+/// Forecast the next `days` study days, starting with the one `now` is in,
+/// of which `today` was studied so far.
+pub fn forecast(
+    states: &HashMap<u64, ReviewState>,
+    scheduler: &dyn Scheduler,
+    limits: &DailyLimits,
+    today: &DailyCounts,
+    now: DateTime<Utc>,
+    rollover_hour: u32,
+    days: usize,
+) -> Vec<ForecastDay> {
+    // Sort by id so the simulation doesn't depend on HashMap order.
+    let mut cards: Vec<ReviewState> = states.values().filter(|s| !s.suspended).cloned().collect();
+    cards.sort_by_key(|s| s.card_id);
+
+    let mut out = Vec::with_capacity(days);
+    let mut day_start = now;
+    let mut counts = today.clone();
+
+    for _ in 0..days {
+        let date = day::study_day(day_start, rollover_hour);
+        let day_end = day::next_day_start(day_start, rollover_hour);
+        // Only today's counts carry over; later days start from zero.
+        counts.roll_over(date);
+
+        // Reviews due before the day ends, most overdue first.
+        let mut due: Vec<usize> = (0..cards.len())
+            .filter(|&i| !cards[i].is_new() && due_at(&cards[i]) < day_end)
+            .collect();
+        due.sort_by_key(|&i| due_at(&cards[i]));
+        due.truncate(counts.reviews_remaining(limits));
+
+        let new: Vec<usize> = (0..cards.len())
+            .filter(|&i| cards[i].is_new() && !cards[i].is_buried(day_start))
+            .take(counts.new_remaining(limits))
+            .collect();
+
+        for &i in due.iter().chain(&new) {
+            let at = due_at(&cards[i]).max(day_start);
+            cards[i] = answer_through_day(scheduler, &cards[i], at, day_end);
+        }
+
+        out.push(ForecastDay {
+            date,
+            reviews: due.len(),
+            new_cards: new.len(),
+        });
+        day_start = day_end;
+    }
+
+    out
+}
+
+/// When a card can next be shown (burying pushes it back).
+fn due_at(state: &ReviewState) -> DateTime<Utc> {
+    state
+        .buried_until
+        .map_or(state.next_review, |b| b.max(state.next_review))
+}
+
+/// Answer "Good" at `at`, then keep answering any same-day learning
+/// repeats until the card is scheduled past `day_end`.
+fn answer_through_day(
+    scheduler: &dyn Scheduler,
+    state: &ReviewState,
+    at: DateTime<Utc>,
+    day_end: DateTime<Utc>,
+) -> ReviewState {
+    let mut next = scheduler.preview(state, Rating::Good, at);
+    for _ in 0..MAX_REPEATS_PER_DAY {
+        if next.next_review >= day_end {
+            break;
+        }
+        let at = next.next_review;
+        next = scheduler.preview(&next, Rating::Good, at);
+    }
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::srs::{FuzzedScheduler, LearningSteps, SchedulerKind, SteppedScheduler};
+    use chrono::{Duration, TimeZone};
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 10, 12, 0, 0).unwrap()
+    }
+
+    fn new_states(n: u64) -> HashMap<u64, ReviewState> {
+        (1..=n)
+            .map(|id| (id, ReviewState::new(id, now())))
+            .collect()
+    }
+
+    #[test]
+    fn new_cards_are_spread_by_the_daily_cap() {
+        let states = new_states(25);
+        let scheduler = SchedulerKind::Sm2.build();
+        let limits = DailyLimits {
+            new_per_day: 10,
+            reviews_per_day: 100,
+        };
+
+        let days = forecast(
+            &states,
+            scheduler.as_ref(),
+            &limits,
+            &DailyCounts::default(),
+            now(),
+            4,
+            4,
+        );
+        let new: Vec<usize> = days.iter().map(|d| d.new_cards).collect();
+        assert_eq!(new, [10, 10, 5, 0]);
+
+        // SM-2 brings each day's batch back one day later.
+        let reviews: Vec<usize> = days.iter().map(|d| d.reviews).collect();
+        assert_eq!(reviews, [0, 10, 10, 5]);
+    }
+
+    #[test]
+    fn overdue_reviews_over_the_cap_spill_into_the_next_day() {
+        let mut states = new_states(30);
+        for state in states.values_mut() {
+            state.phase = crate::model::CardPhase::Review;
+            state.repetitions = 3;
+            state.interval_days = 30.0;
+            state.last_review = Some(now() - Duration::days(40));
+            state.next_review = now() - Duration::days(10);
+        }
+        let scheduler = SteppedScheduler::new(SchedulerKind::Sm2.build(), LearningSteps::default());
+        let limits = DailyLimits {
+            new_per_day: 0,
+            reviews_per_day: 20,
+        };
+
+        let days = forecast(
+            &states,
+            &scheduler,
+            &limits,
+            &DailyCounts::default(),
+            now(),
+            4,
+            3,
+        );
+        let reviews: Vec<usize> = days.iter().map(|d| d.reviews).collect();
+        assert_eq!(reviews, [20, 10, 0]);
+    }
+
+    #[test]
+    fn today_only_gets_what_is_left_of_its_caps() {
+        let states = new_states(25);
+        let scheduler = SchedulerKind::Sm2.build();
+        let limits = DailyLimits {
+            new_per_day: 10,
+            reviews_per_day: 100,
+        };
+        let today = DailyCounts {
+            day: Some(day::study_day(now(), 4)),
+            new_studied: 7,
+            reviews_done: 0,
+        };
+
+        let days = forecast(&states, scheduler.as_ref(), &limits, &today, now(), 4, 4);
+        let new: Vec<usize> = days.iter().map(|d| d.new_cards).collect();
+        assert_eq!(new, [3, 10, 10, 2]);
+
+        // Counts from another day don't hold today back.
+        let stale = DailyCounts {
+            day: Some(day::study_day(now(), 4).pred_opt().unwrap()),
+            ..today
+        };
+        let days = forecast(&states, scheduler.as_ref(), &limits, &stale, now(), 4, 1);
+        assert_eq!(days[0].new_cards, 10);
+    }
+
+    #[test]
+    fn forecast_ignores_interval_fuzz() {
+        let mut states = new_states(20);
+        for (i, state) in states.values_mut().enumerate() {
+            state.phase = crate::model::CardPhase::Review;
+            state.repetitions = 5;
+            state.interval_days = 20.0 + i as f64;
+            state.last_review = Some(now() - Duration::days(20));
+            state.next_review = now();
+        }
+        let limits = DailyLimits::default();
+        let plain = SchedulerKind::Sm2.build();
+        let fuzzed = FuzzedScheduler::seeded(SchedulerKind::Sm2.build(), Some(7));
+
+        let expected = forecast(
+            &states,
+            plain.as_ref(),
+            &limits,
+            &DailyCounts::default(),
+            now(),
+            4,
+            90,
+        );
+        for _ in 0..3 {
+            let days = forecast(
+                &states,
+                &fuzzed,
+                &limits,
+                &DailyCounts::default(),
+                now(),
+                4,
+                90,
+            );
+            assert_eq!(days, expected);
+        }
+    }
+}
@@ -0,0 +1,240 @@
+// src/srs/fsrs.rs
+//
+// FSRS (Free Spaced Repetition Scheduler), version 4.5.
+//
+// FSRS models each card's memory with two numbers:
+//   - stability  (S): days until recall probability drops to 90%
+//   - difficulty (D): 1.0 (easy) ..= 10.0 (hard)
+//
+// Recall probability after `t` days is the power forgetting curve
+//   R(t, S) = (1 + FACTOR * t / S) ^ DECAY
+// and the next interval is chosen so that R falls to the desired
+// retention exactly when the card is shown again.
+
+use chrono::{DateTime, Utc};
+
+use crate::model::ReviewState;
+
+use super::sm2::days_to_duration;
+
+/// Exponent of the FSRS-4.5 forgetting curve.
+pub const DECAY: f64 = -0.5;
+
+/// Chosen so that R(S, S) = 0.9.
+pub const FACTOR: f64 = 19.0 / 81.0;
+
+/// Default FSRS-4.5 weights (trained on a large public review dataset).
+pub const DEFAULT_WEIGHTS: [f64; 17] = [
+    0.4872, 1.4003, 3.7145, 13.8206, 5.1618, 1.2298, 0.8975, 0.031, 1.6474, 0.1367, 1.0461, 2.1072,
+    0.0793, 0.3246, 1.587, 0.2272, 2.8755,
+];
+
+/// Default probability of recall we aim for when a card comes back.
+pub const DEFAULT_DESIRED_RETENTION: f64 = 0.9;
+
+/// Longest interval we'll ever hand out (about 100 years).
+pub const MAX_INTERVAL_DAYS: f64 = 36_500.0;
+
+/// FSRS grades: 1 = Again, 2 = Hard, 3 = Good, 4 = Easy
+/// (see `Rating::fsrs_grade`).
+pub type Grade = u8;
+
+/// Probability of recalling a card with stability `stability`
+/// after `elapsed_days`.
+pub fn retrievability(elapsed_days: f64, stability: f64) -> f64 {
+    (1.0 + FACTOR * elapsed_days.max(0.0) / stability).powf(DECAY)
+}
+
+/// Interval (in days, unrounded) after which recall probability falls
+/// to `desired_retention`.
+pub fn interval_for(stability: f64, desired_retention: f64) -> f64 {
+    stability / FACTOR * (desired_retention.powf(1.0 / DECAY) - 1.0)
+}
+
+/// FSRS parameters plus the retention target used to derive intervals.
+#[derive(Debug, Clone)]
+pub struct FsrsParams {
+    pub weights: [f64; 17],
+    pub desired_retention: f64,
+}
+
+impl Default for FsrsParams {
+    fn default() -> Self {
+        Self {
+            weights: DEFAULT_WEIGHTS,
+            desired_retention: DEFAULT_DESIRED_RETENTION,
+        }
+    }
+}
+
+impl FsrsParams {
+    fn initial_stability(&self, grade: Grade) -> f64 {
+        self.weights[usize::from(grade.clamp(1, 4)) - 1].max(0.1)
+    }
+
+    fn initial_difficulty(&self, grade: Grade) -> f64 {
+        let w = &self.weights;
+        (w[4] - f64::from(i32::from(grade) - 3) * w[5]).clamp(1.0, 10.0)
+    }
+
+    fn next_difficulty(&self, difficulty: f64, grade: Grade) -> f64 {
+        let w = &self.weights;
+        let shifted = difficulty - w[6] * f64::from(i32::from(grade) - 3);
+        // Mean reversion towards the difficulty of a fresh "Good" card.
+        (w[7] * self.initial_difficulty(3) + (1.0 - w[7]) * shifted).clamp(1.0, 10.0)
+    }
+
+    fn recall_stability(&self, d: f64, s: f64, r: f64, grade: Grade) -> f64 {
+        let w = &self.weights;
+        let hard_penalty = if grade == 2 { w[15] } else { 1.0 };
+        let easy_bonus = if grade == 4 { w[16] } else { 1.0 };
+
+        s * (w[8].exp()
+            * (11.0 - d)
+            * s.powf(-w[9])
+            * ((w[10] * (1.0 - r)).exp() - 1.0)
+            * hard_penalty
+            * easy_bonus
+            + 1.0)
+    }
+
+    fn forget_stability(&self, d: f64, s: f64, r: f64) -> f64 {
+        let w = &self.weights;
+        let s_new =
+            w[11] * d.powf(-w[12]) * ((s + 1.0).powf(w[13]) - 1.0) * (w[14] * (1.0 - r)).exp();
+        // A lapse can never make a card *more* stable than it was.
+        s_new.min(s)
+    }
+
+    /// Memory state after one review. `memory` is the `(stability,
+    /// difficulty)` before the review, `None` for a card's first one.
+    pub fn next_memory(
+        &self,
+        memory: Option<(f64, f64)>,
+        elapsed_days: f64,
+        grade: Grade,
+    ) -> (f64, f64) {
+        let grade = grade.clamp(1, 4);
+        let (stability, difficulty) = match memory {
+            Some((s, d)) => {
+                let r = retrievability(elapsed_days, s);
+                let d_new = self.next_difficulty(d, grade);
+                let s_new = if grade == 1 {
+                    self.forget_stability(d, s, r)
+                } else {
+                    self.recall_stability(d, s, r, grade)
+                };
+                (s_new, d_new)
+            }
+            None => (
+                self.initial_stability(grade),
+                self.initial_difficulty(grade),
+            ),
+        };
+        (stability.max(0.1), difficulty)
+    }
+
+    /// Apply one review with an FSRS grade (1..=4) and return the new state.
+    pub fn schedule(&self, state: &ReviewState, grade: Grade, now: DateTime<Utc>) -> ReviewState {
+        let grade = grade.clamp(1, 4);
+        let mut next = state.clone();
+
+        let memory = match (state.stability, state.difficulty) {
+            (Some(s), Some(d)) => Some((s, d)),
+            // Card was scheduled by SM-2 before: seed the memory state
+            // from its current interval, then treat this as a normal review.
+            _ if state.repetitions > 0 && state.interval_days > 0.0 => {
+                Some((state.interval_days, self.initial_difficulty(3)))
+            }
+            // Brand-new card.
+            _ => None,
+        };
+        let (stability, difficulty) = self.next_memory(memory, elapsed_days(state, now), grade);
+
+        let interval = interval_for(stability, self.desired_retention)
+            .round()
+            .clamp(1.0, MAX_INTERVAL_DAYS);
+
+        next.stability = Some(stability);
+        next.difficulty = Some(difficulty);
+        next.interval_days = interval;
+        next.repetitions = if grade == 1 { 0 } else { state.repetitions + 1 };
+        next.last_review = Some(now);
+        next.next_review = now + days_to_duration(interval);
+        next
+    }
+}
+
+/// Days since the card was last reviewed. Falls back to the scheduled
+/// interval for states saved before `last_review` was tracked.
+fn elapsed_days(state: &ReviewState, now: DateTime<Utc>) -> f64 {
+    match state.last_review {
+        Some(last) => (now - last).num_seconds().max(0) as f64 / 86_400.0,
+        None => state.interval_days,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 5, 1, 9, 30, 0).unwrap()
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn first_review_matches_fsrs_4_5_reference_values() {
+        let params = FsrsParams::default();
+        // S0 = w[G-1], D0 = w4 - (G-3) * w5.
+        let expected = [
+            (1, 0.4872, 7.6214),
+            (2, 1.4003, 6.3916),
+            (3, 3.7145, 5.1618),
+            (4, 13.8206, 3.932),
+        ];
+        for (grade, stability, difficulty) in expected {
+            let (s, d) = params.next_memory(None, 0.0, grade);
+            assert!(close(s, stability), "S0({grade}) = {s}");
+            assert!(close(d, difficulty), "D0({grade}) = {d}");
+        }
+    }
+
+    #[test]
+    fn stability_is_the_interval_at_90_percent() {
+        assert!(close(retrievability(10.0, 10.0), 0.9));
+        assert!(close(interval_for(10.0, 0.9), 10.0));
+        assert!(interval_for(10.0, 0.8) > 10.0);
+        assert!(interval_for(10.0, 0.95) < 10.0);
+    }
+
+    #[test]
+    fn recall_grows_the_interval_and_a_lapse_shrinks_it() {
+        let params = FsrsParams::default();
+        let memory = Some((10.0, 5.0));
+        let interval = |grade| {
+            let (s, _) = params.next_memory(memory, 10.0, grade);
+            interval_for(s, params.desired_retention)
+        };
+
+        let before = interval_for(10.0, params.desired_retention);
+        assert!(interval(1) < before);
+        assert!(interval(2) > before);
+        assert!(interval(3) > interval(2));
+        assert!(interval(4) > interval(3));
+
+        // The same through `schedule`, with a real review history.
+        let mut state = params.schedule(&ReviewState::new(1, now()), 3, now());
+        let first = state.interval_days;
+        state = params.schedule(&state, 3, state.next_review);
+        assert!(state.interval_days > first);
+        let grown = state.interval_days;
+        state = params.schedule(&state, 1, state.next_review + Duration::days(2));
+        assert!(state.interval_days < grown);
+        assert_eq!(state.repetitions, 0);
+    }
+}
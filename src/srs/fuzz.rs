@@ -0,0 +1,177 @@
+// src/srs/fuzz.rs
+//
+// Interval fuzz.
+//
+// Cards introduced together would otherwise keep falling due together.
+// `FuzzedScheduler` nudges every day-scale interval by a few percent in
+// either direction. The random number generator is passed in, so a
+// seeded `StdRng` gives the same schedule every run (tests rely on this).
+
+use std::cell::RefCell;
+
+use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+
+use crate::model::ReviewState;
+
+use super::{sm2::days_to_duration, Rating, Scheduler, SchedulerKind};
+
+/// Intervals shorter than this (in days) are never fuzzed.
+pub const MIN_FUZZ_INTERVAL: f64 = 2.5;
+
+/// Largest fuzz (in days) the given interval may receive in either
+/// direction: 15% up to a week, 10% up to 20 days, 5% beyond, and
+/// always at least one day.
+pub fn fuzz_range(interval_days: f64) -> f64 {
+    if interval_days < MIN_FUZZ_INTERVAL {
+        return 0.0;
+    }
+
+    let pct = if interval_days < 7.0 {
+        0.15
+    } else if interval_days < 20.0 {
+        0.10
+    } else {
+        0.05
+    };
+    (interval_days * pct).max(1.0)
+}
+
+/// Randomly move `interval_days` within its fuzz range (whole days, and
+/// never below one day).
+pub fn fuzz_interval(interval_days: f64, rng: &mut dyn RngCore) -> f64 {
+    let range = fuzz_range(interval_days);
+    if range == 0.0 {
+        return interval_days;
+    }
+    rng.gen_range(interval_days - range..=interval_days + range)
+        .round()
+        .max(1.0)
+}
+
+/// Adds interval fuzz on top of another scheduler.
+pub struct FuzzedScheduler {
+    inner: Box<dyn Scheduler>,
+    rng: RefCell<Box<dyn RngCore>>,
+}
+
+impl FuzzedScheduler {
+    pub fn new(inner: Box<dyn Scheduler>, rng: Box<dyn RngCore>) -> Self {
+        Self {
+            inner,
+            rng: RefCell::new(rng),
+        }
+    }
+
+    /// Fuzz with a `StdRng` seeded from `seed`, or from the OS if `None`.
+    pub fn seeded(inner: Box<dyn Scheduler>, seed: Option<u64>) -> Self {
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Self::new(inner, Box::new(rng))
+    }
+}
+
+impl Scheduler for FuzzedScheduler {
+    fn kind(&self) -> SchedulerKind {
+        self.inner.kind()
+    }
+
+    fn is_due(&self, state: &ReviewState, now: DateTime<Utc>) -> bool {
+        self.inner.is_due(state, now)
+    }
+
+    fn review(&self, state: &ReviewState, rating: Rating, now: DateTime<Utc>) -> ReviewState {
+        let mut next = self.inner.review(state, rating, now);
+
+        // Learning steps are minutes apart; only day intervals get fuzz.
+        if !next.is_learning() {
+            let fuzzed = fuzz_interval(next.interval_days, self.rng.borrow_mut().as_mut());
+            if fuzzed != next.interval_days {
+                next.interval_days = fuzzed;
+                next.next_review = now + days_to_duration(fuzzed);
+            }
+        }
+        next
+    }
+
+    /// The unfuzzed review: previews must not use up the seeded RNG.
+    fn preview(&self, state: &ReviewState, rating: Rating, now: DateTime<Utc>) -> ReviewState {
+        self.inner.preview(state, rating, now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn t0() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap()
+    }
+
+    /// Intervals for `n` cards, each answered "Good" `reviews` times.
+    fn schedule_cards(seed: u64, n: u64, reviews: usize) -> Vec<f64> {
+        let scheduler = FuzzedScheduler::seeded(SchedulerKind::Sm2.build(), Some(seed));
+        (1..=n)
+            .map(|id| {
+                let mut state = ReviewState::new(id, t0());
+                for _ in 0..reviews {
+                    state = scheduler.review(&state, Rating::Good, state.next_review);
+                }
+                state.interval_days
+            })
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_gives_same_intervals() {
+        assert_eq!(schedule_cards(42, 20, 4), schedule_cards(42, 20, 4));
+        assert_ne!(schedule_cards(42, 20, 4), schedule_cards(7, 20, 4));
+    }
+
+    #[test]
+    fn fuzz_stays_within_range() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let fuzzed: Vec<f64> = (0..100).map(|_| fuzz_interval(30.0, &mut rng)).collect();
+
+        // ±5% of 30 days, plus rounding to whole days.
+        let limit = fuzz_range(30.0) + 0.5;
+        assert!(fuzzed.iter().all(|&d| (d - 30.0).abs() <= limit));
+        assert!(fuzzed.iter().any(|&d| d != fuzzed[0]));
+    }
+
+    #[test]
+    fn short_intervals_are_not_fuzzed() {
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(fuzz_interval(1.0, &mut rng), 1.0);
+        assert_eq!(fuzz_range(2.0), 0.0);
+        assert_eq!(fuzz_range(100.0), 5.0);
+    }
+
+    #[test]
+    fn previews_are_unfuzzed_and_leave_the_schedule_alone() {
+        let mut state = ReviewState::new(1, t0());
+        for _ in 0..4 {
+            state = SchedulerKind::Sm2
+                .build()
+                .review(&state, Rating::Good, state.next_review);
+        }
+        let now = state.next_review;
+
+        let fuzzed = FuzzedScheduler::seeded(SchedulerKind::Sm2.build(), Some(42));
+        let plain = SchedulerKind::Sm2.build().review(&state, Rating::Good, now);
+        for _ in 0..10 {
+            let preview = fuzzed.preview(&state, Rating::Good, now);
+            assert_eq!(preview.interval_days, plain.interval_days);
+        }
+
+        let untouched = FuzzedScheduler::seeded(SchedulerKind::Sm2.build(), Some(42));
+        assert_eq!(
+            fuzzed.review(&state, Rating::Good, now).interval_days,
+            untouched.review(&state, Rating::Good, now).interval_days
+        );
+    }
+}
@@ -0,0 +1,121 @@
+// src/srs/leech.rs
+//
+// Leeches: cards that keep getting forgotten.
+//
+// Once a card's lapse count reaches the threshold it is tagged "leech"
+// and (optionally) suspended, so it stops eating review time until the
+// learner rewrites or unsuspends it.
+
+use crate::model::ReviewState;
+
+/// Tag added to a card's `ReviewState` when it becomes a leech.
+pub const LEECH_TAG: &str = "leech";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeechSettings {
+    /// Lapses after which a card counts as a leech.
+    pub threshold: u32,
+    /// Suspend the card as soon as it becomes a leech.
+    pub suspend: bool,
+}
+
+impl Default for LeechSettings {
+    fn default() -> Self {
+        Self {
+            threshold: 8,
+            suspend: true,
+        }
+    }
+}
+
+impl LeechSettings {
+    /// Tag (and maybe suspend) `state` if it has just reached the
+    /// threshold. Returns `true` only the first time a card becomes a leech.
+    pub fn check(&self, state: &mut ReviewState) -> bool {
+        if self.threshold == 0 || state.lapses < self.threshold || state.has_tag(LEECH_TAG) {
+            return false;
+        }
+
+        state.tags.push(LEECH_TAG.to_string());
+        if self.suspend {
+            state.suspended = true;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn state_with_lapses(lapses: u32) -> ReviewState {
+        let now = Utc.with_ymd_and_hms(2025, 5, 1, 9, 30, 0).unwrap();
+        let mut state = ReviewState::new(1, now);
+        state.lapses = lapses;
+        state
+    }
+
+    #[test]
+    fn reaching_the_threshold_tags_and_suspends() {
+        let settings = LeechSettings::default();
+
+        let mut state = state_with_lapses(7);
+        assert!(!settings.check(&mut state));
+        assert!(!state.has_tag(LEECH_TAG));
+        assert!(!state.suspended);
+
+        state.lapses = 8;
+        assert!(settings.check(&mut state));
+        assert_eq!(state.tags, [LEECH_TAG]);
+        assert!(state.suspended);
+    }
+
+    #[test]
+    fn tag_only_leaves_the_card_in_rotation() {
+        let settings = LeechSettings {
+            threshold: 3,
+            suspend: false,
+        };
+        let mut state = state_with_lapses(3);
+        assert!(settings.check(&mut state));
+        assert!(state.has_tag(LEECH_TAG));
+        assert!(!state.suspended);
+    }
+
+    #[test]
+    fn leech_is_only_flagged_once() {
+        let settings = LeechSettings {
+            threshold: 2,
+            suspend: false,
+        };
+        let mut state = state_with_lapses(2);
+        assert!(settings.check(&mut state));
+
+        for lapses in 3..6 {
+            state.lapses = lapses;
+            assert!(!settings.check(&mut state));
+        }
+        assert_eq!(state.tags, [LEECH_TAG]);
+
+        // Unsuspending a suspended leech doesn't re-suspend it either.
+        let settings = LeechSettings::default();
+        let mut state = state_with_lapses(8);
+        assert!(settings.check(&mut state));
+        state.suspended = false;
+        state.lapses = 9;
+        assert!(!settings.check(&mut state));
+        assert!(!state.suspended);
+    }
+
+    #[test]
+    fn zero_threshold_never_flags() {
+        let settings = LeechSettings {
+            threshold: 0,
+            suspend: true,
+        };
+        let mut state = state_with_lapses(50);
+        assert!(!settings.check(&mut state));
+        assert!(state.tags.is_empty());
+    }
+}
@@ -0,0 +1,169 @@
+// src/srs/limits.rs
+//
+// Daily new / review caps.
+//
+// `DailyCounts` records how many new cards and reviews were studied on
+// the current study day (see `day.rs`). It's saved next to the deck's
+// progress file so the caps still hold after a restart.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::model::ReviewState;
+
+/// Per-day caps for one deck.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DailyLimits {
+    pub new_per_day: usize,
+    pub reviews_per_day: usize,
+}
+
+impl Default for DailyLimits {
+    fn default() -> Self {
+        Self {
+            new_per_day: 20,
+            reviews_per_day: 200,
+        }
+    }
+}
+
+/// What has been studied so far on `day`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DailyCounts {
+    pub day: Option<NaiveDate>,
+    pub new_studied: usize,
+    pub reviews_done: usize,
+}
+
+impl DailyCounts {
+    /// Start from zero if `today` is a different study day than the one
+    /// these counts belong to. Returns `true` if anything was reset.
+    pub fn roll_over(&mut self, today: NaiveDate) -> bool {
+        if self.day == Some(today) {
+            return false;
+        }
+        *self = Self {
+            day: Some(today),
+            ..Self::default()
+        };
+        true
+    }
+
+    /// New cards still allowed today.
+    pub fn new_remaining(&self, limits: &DailyLimits) -> usize {
+        limits.new_per_day.saturating_sub(self.new_studied)
+    }
+
+    /// Reviews still allowed today.
+    pub fn reviews_remaining(&self, limits: &DailyLimits) -> usize {
+        limits.reviews_per_day.saturating_sub(self.reviews_done)
+    }
+
+    /// Count one answer, given the card's state *before* it was answered
+    /// and whether the card was already answered this session. Repeats
+    /// (learning steps, missed cards coming back) don't count again.
+    pub fn record(&mut self, before: &ReviewState, repeat: bool) {
+        if repeat {
+            return;
+        }
+        if before.is_new() {
+            self.new_studied += 1;
+        } else if is_review(before) {
+            self.reviews_done += 1;
+        }
+    }
+}
+
+/// Whether a card in `state` is one of the day's reviews: any card that
+/// has been studied before, in whatever phase. The session queue caps the
+/// due cards it takes by this rule, and `DailyCounts::record` counts them
+/// by it.
+pub fn is_review(state: &ReviewState) -> bool {
+    !state.is_new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::CardPhase;
+    use chrono::{TimeZone, Utc};
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 5, d).unwrap()
+    }
+
+    #[test]
+    fn roll_over_resets_the_counts_on_a_new_day() {
+        let mut counts = DailyCounts {
+            day: Some(day(1)),
+            new_studied: 5,
+            reviews_done: 40,
+        };
+        assert!(!counts.roll_over(day(1)));
+        assert_eq!((counts.new_studied, counts.reviews_done), (5, 40));
+
+        assert!(counts.roll_over(day(2)));
+        assert_eq!(
+            counts,
+            DailyCounts {
+                day: Some(day(2)),
+                new_studied: 0,
+                reviews_done: 0,
+            }
+        );
+
+        // Counts saved without a day always start afresh.
+        let mut counts = DailyCounts {
+            day: None,
+            new_studied: 3,
+            reviews_done: 3,
+        };
+        assert!(counts.roll_over(day(1)));
+        assert_eq!(counts.new_studied, 0);
+    }
+
+    #[test]
+    fn remaining_saturates_at_zero() {
+        let limits = DailyLimits {
+            new_per_day: 10,
+            reviews_per_day: 50,
+        };
+        let mut counts = DailyCounts::default();
+        assert_eq!(counts.new_remaining(&limits), 10);
+        assert_eq!(counts.reviews_remaining(&limits), 50);
+
+        counts.new_studied = 7;
+        counts.reviews_done = 50;
+        assert_eq!(counts.new_remaining(&limits), 3);
+        assert_eq!(counts.reviews_remaining(&limits), 0);
+
+        // Over the cap, e.g. after the limits were lowered.
+        counts.new_studied = 25;
+        counts.reviews_done = 80;
+        assert_eq!(counts.new_remaining(&limits), 0);
+        assert_eq!(counts.reviews_remaining(&limits), 0);
+    }
+
+    #[test]
+    fn record_counts_each_card_once_per_session() {
+        let now = Utc.with_ymd_and_hms(2025, 5, 1, 9, 30, 0).unwrap();
+        let mut counts = DailyCounts::default();
+
+        let mut state = ReviewState::new(1, now);
+        counts.record(&state, false);
+        state.phase = CardPhase::Learning;
+        state.repetitions = 1;
+        counts.record(&state, true);
+
+        // Left in learning yesterday, due again today.
+        state.last_review = Some(now);
+        counts.record(&state, false);
+
+        state.phase = CardPhase::Review;
+        counts.record(&state, false);
+        counts.record(&state, true);
+
+        assert_eq!((counts.new_studied, counts.reviews_done), (1, 2));
+    }
+}
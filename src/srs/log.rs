@@ -0,0 +1,239 @@
+// src/srs/log.rs
+//
+// Append-only review log.
+//
+// Every answer is written as one JSON object per line to
+// `progress/review-log.jsonl`. The file is never rewritten, so it's the
+// full history behind the `ReviewState`s: statistics, undo and parameter
+// fitting all read from it.
+
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::model::{CardPhase, Direction, ReviewState};
+
+use super::Rating;
+
+/// File name of the review log inside the progress directory.
+pub const REVIEW_LOG_FILE: &str = "review-log.jsonl";
+
+/// Review log path inside `dir` (normally `progress::PROGRESS_DIR`).
+pub fn review_log_path(dir: &Path) -> PathBuf {
+    dir.join(REVIEW_LOG_FILE)
+}
+
+/// Where a log entry came from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewSource {
+    /// A normal review.
+    #[default]
+    Study,
+    /// A matching-game round: a low-weight signal, not a recall test
+    /// (see `matching::weighted_review`).
+    Matching,
+}
+
+/// One answered card.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewLogEntry {
+    pub card_id: u64,
+    /// Which way the card was asked (entries from before directions
+    /// existed were all `Reverse`).
+    #[serde(default)]
+    pub direction: Direction,
+    /// Deck key (the deck file's stem).
+    pub deck: String,
+    pub timestamp: DateTime<Utc>,
+    pub rating: Rating,
+    /// The option the learner picked or the answer they typed (`None` when
+    /// the card was self-graded).
+    #[serde(default)]
+    pub chosen: Option<String>,
+    pub response_secs: f32,
+
+    /// Phase the card was in when it was answered.
+    #[serde(default)]
+    pub phase: CardPhase,
+    /// Days since the previous review (`None` for a card's first review).
+    #[serde(default)]
+    pub elapsed_days: Option<f64>,
+
+    pub interval_before: f64,
+    pub interval_after: f64,
+    pub ease_before: f64,
+    pub ease_after: f64,
+
+    #[serde(default)]
+    pub source: ReviewSource,
+}
+
+impl ReviewLogEntry {
+    /// Build an entry from a card's state before and after the answer.
+    pub fn new(
+        deck: &str,
+        before: &ReviewState,
+        after: &ReviewState,
+        rating: Rating,
+        chosen: Option<String>,
+        response_secs: f32,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let (card_id, direction) = Direction::split_key(before.card_id);
+        Self {
+            card_id,
+            direction,
+            deck: deck.to_string(),
+            timestamp: now,
+            rating,
+            chosen,
+            response_secs,
+            phase: before.phase,
+            elapsed_days: before
+                .last_review
+                .map(|last| (now - last).num_seconds().max(0) as f64 / 86_400.0),
+            interval_before: before.interval_days,
+            interval_after: after.interval_days,
+            ease_before: before.ease_factor,
+            ease_after: after.ease_factor,
+            source: ReviewSource::Study,
+        }
+    }
+}
+
+/// Append one entry to the log at `path`, creating the file if needed.
+pub fn append(path: &Path, entry: &ReviewLogEntry) -> anyhow::Result<()> {
+    if let Some(dir) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create log dir {}", dir.display()))?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .read(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open review log {}", path.display()))?;
+
+    // A crash mid-append can leave a torn last line: start on a fresh one
+    // so only that line is lost, not this entry too.
+    let mut line = String::new();
+    if file.seek(SeekFrom::End(0))? > 0 {
+        let mut last = [0u8];
+        file.seek(SeekFrom::End(-1))?;
+        file.read_exact(&mut last)?;
+        if last[0] != b'\n' {
+            line.push('\n');
+        }
+    }
+    line.push_str(&serde_json::to_string(entry)?);
+    line.push('\n');
+
+    // One write per line so a crash can at worst leave a partial last line.
+    file.write_all(line.as_bytes())?;
+    file.sync_data()?;
+    Ok(())
+}
+
+/// The review history, as read back by `read_all`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReviewLog {
+    pub entries: Vec<ReviewLogEntry>,
+    /// Lines that didn't parse (e.g. a torn line) and were left out, for
+    /// the caller to report.
+    pub skipped: usize,
+}
+
+/// Read the whole log. A missing file is an empty history; lines that
+/// don't parse are skipped and counted.
+pub fn read_all(path: &Path) -> anyhow::Result<ReviewLog> {
+    if !path.exists() {
+        return Ok(ReviewLog::default());
+    }
+
+    let file = fs::File::open(path)
+        .with_context(|| format!("Failed to open review log {}", path.display()))?;
+
+    let mut log = ReviewLog::default();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(&line) {
+            Ok(entry) => log.entries.push(entry),
+            Err(_) => log.skipped += 1,
+        }
+    }
+    Ok(log)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 5, 1, 9, 30, 0).unwrap()
+    }
+
+    fn entry(key: u64, rating: Rating, minutes: i64) -> ReviewLogEntry {
+        let before = ReviewState::new(key, now());
+        let mut after = before.clone();
+        after.interval_days = 1.0;
+        ReviewLogEntry::new(
+            "animals",
+            &before,
+            &after,
+            rating,
+            Some("cat".to_string()),
+            2.5,
+            now() + Duration::minutes(minutes),
+        )
+    }
+
+    #[test]
+    fn entries_round_trip_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = review_log_path(&dir.path().join("progress"));
+        assert_eq!(read_all(&path).unwrap(), ReviewLog::default());
+
+        let entries = [
+            entry(1, Rating::Good, 0),
+            entry(Direction::Forward.key(2), Rating::Again, 1),
+        ];
+        for e in &entries {
+            append(&path, e).unwrap();
+        }
+
+        let log = read_all(&path).unwrap();
+        assert_eq!(log.entries, entries);
+        assert_eq!(log.skipped, 0);
+        assert_eq!(log.entries[1].card_id, 2);
+        assert_eq!(log.entries[1].direction, Direction::Forward);
+    }
+
+    #[test]
+    fn torn_line_is_skipped_and_does_not_swallow_the_next_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = review_log_path(dir.path());
+        append(&path, &entry(1, Rating::Good, 0)).unwrap();
+
+        // A crash mid-append: half a line, no newline.
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"card_id": 2, "deck": "ani"#).unwrap();
+        drop(file);
+
+        append(&path, &entry(3, Rating::Easy, 2)).unwrap();
+
+        let log = read_all(&path).unwrap();
+        let ids: Vec<u64> = log.entries.iter().map(|e| e.card_id).collect();
+        assert_eq!(ids, [1, 3]);
+        assert_eq!(log.skipped, 1);
+    }
+}
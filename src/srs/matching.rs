@@ -0,0 +1,344 @@
+// src/srs/matching.rs
+//
+// The matching game: a round lays out a few cards' terms and definitions
+// in two shuffled columns and the learner pairs them up.
+//
+// Mistakes and time make the round's score. Each card's mistakes also
+// become a review signal, but a weak one (`SIGNAL_WEIGHT`): picking a
+// pair out of a handful is much easier than recalling the answer cold.
+
+use chrono::{DateTime, Duration, Utc};
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::model::{Card, CardPhase, ReviewState};
+
+use super::{Rating, Scheduler};
+
+/// Fewest pairs in a round.
+pub const MIN_PAIRS: usize = 3;
+
+/// Most pairs in a round (one per number key).
+pub const MAX_PAIRS: usize = 8;
+
+/// Share of a real review's effect a matching result has.
+pub const SIGNAL_WEIGHT: f64 = 0.25;
+
+/// Seconds per pair a round can take before the time bonus runs out.
+const PAR_SECS_PER_PAIR: f32 = 5.0;
+
+/// Result of trying one term against one definition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PairOutcome {
+    Matched,
+    Mismatched,
+}
+
+/// One round of the game.
+#[derive(Debug, Clone)]
+pub struct MatchingRound {
+    /// Review key of each card in the round, and the card as asked.
+    pub keys: Vec<u64>,
+    pub cards: Vec<Card>,
+    /// Display order of the term and definition columns (indices into
+    /// `cards`).
+    pub terms: Vec<usize>,
+    pub definitions: Vec<usize>,
+    /// Wrong pairings each card was part of.
+    pub mistakes: Vec<u32>,
+    /// The term / definition picked so far for the next pair.
+    pub selected_term: Option<usize>,
+    pub selected_definition: Option<usize>,
+    /// The last wrong pair (term, definition), until the next pick.
+    pub last_mismatch: Option<(usize, usize)>,
+    term_matched: Vec<bool>,
+    definition_matched: Vec<bool>,
+}
+
+impl MatchingRound {
+    /// A round over `cards` (review key + card as asked), with both
+    /// columns shuffled.
+    pub fn new(cards: Vec<(u64, Card)>, rng: &mut dyn RngCore) -> Self {
+        let n = cards.len();
+        let (keys, cards) = cards.into_iter().unzip();
+        let mut terms: Vec<usize> = (0..n).collect();
+        let mut definitions = terms.clone();
+        terms.shuffle(rng);
+        definitions.shuffle(rng);
+
+        Self {
+            keys,
+            cards,
+            terms,
+            definitions,
+            mistakes: vec![0; n],
+            selected_term: None,
+            selected_definition: None,
+            last_mismatch: None,
+            term_matched: vec![false; n],
+            definition_matched: vec![false; n],
+        }
+    }
+
+    pub fn is_term_matched(&self, card: usize) -> bool {
+        self.term_matched[card]
+    }
+
+    pub fn is_definition_matched(&self, card: usize) -> bool {
+        self.definition_matched[card]
+    }
+
+    /// Pick the term of `card`; completes a pair if a definition is
+    /// already picked. Matched terms can't be picked again.
+    pub fn select_term(&mut self, card: usize) -> Option<PairOutcome> {
+        if self.term_matched.get(card).copied().unwrap_or(true) {
+            return None;
+        }
+        self.last_mismatch = None;
+        self.selected_term = Some(card);
+        self.try_pair()
+    }
+
+    /// Pick the definition of `card`; see `select_term`.
+    pub fn select_definition(&mut self, card: usize) -> Option<PairOutcome> {
+        if self.definition_matched.get(card).copied().unwrap_or(true) {
+            return None;
+        }
+        self.last_mismatch = None;
+        self.selected_definition = Some(card);
+        self.try_pair()
+    }
+
+    /// A term and a definition pair up if they belong to the same card, or
+    /// to cards with the same term or definition (which look identical).
+    /// A wrong pair counts as a mistake for both cards.
+    fn try_pair(&mut self) -> Option<PairOutcome> {
+        let (Some(t), Some(d)) = (self.selected_term, self.selected_definition) else {
+            return None;
+        };
+        self.selected_term = None;
+        self.selected_definition = None;
+
+        let (a, b) = (&self.cards[t], &self.cards[d]);
+        if a.term == b.term || a.definition == b.definition {
+            self.term_matched[t] = true;
+            self.definition_matched[d] = true;
+            Some(PairOutcome::Matched)
+        } else {
+            self.mistakes[t] += 1;
+            self.mistakes[d] += 1;
+            self.last_mismatch = Some((t, d));
+            Some(PairOutcome::Mismatched)
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.term_matched.iter().all(|&m| m)
+    }
+
+    /// Wrong pairs tried so far (each counts once, not once per card).
+    pub fn total_mistakes(&self) -> u32 {
+        self.mistakes.iter().sum::<u32>() / 2
+    }
+
+    /// Each card's review key with the rating its mistakes earn.
+    pub fn ratings(&self) -> impl Iterator<Item = (u64, Rating)> + '_ {
+        self.keys
+            .iter()
+            .zip(&self.mistakes)
+            .map(|(&key, &mistakes)| (key, signal_rating(mistakes)))
+    }
+}
+
+/// Rating a card earns in a round: `Good` if it was never part of a wrong
+/// pair, `Hard` after one, `Again` after more.
+pub fn signal_rating(mistakes: u32) -> Rating {
+    match mistakes {
+        0 => Rating::Good,
+        1 => Rating::Hard,
+        _ => Rating::Again,
+    }
+}
+
+/// `state` moved `weight` of the way towards what `scheduler` would make
+/// of a real review rated `rating` at `now`. Only graduated cards change:
+/// a match is no way to learn a new card, and learning steps are too
+/// short to nudge. The card isn't marked as reviewed, and the review is
+/// only previewed, so a seeded interval fuzz isn't drawn from.
+pub fn weighted_review(
+    scheduler: &dyn Scheduler,
+    state: &ReviewState,
+    rating: Rating,
+    weight: f64,
+    now: DateTime<Utc>,
+) -> ReviewState {
+    if state.phase != CardPhase::Review || state.suspended {
+        return state.clone();
+    }
+
+    let full = scheduler.preview(state, rating, now);
+    let lerp = |a: f64, b: f64| a + (b - a) * weight;
+    let lerp_opt = |a: Option<f64>, b: Option<f64>| match (a, b) {
+        (Some(a), Some(b)) => Some(lerp(a, b)),
+        (a, _) => a,
+    };
+    let shift = (full.next_review - state.next_review).num_seconds() as f64 * weight;
+
+    ReviewState {
+        interval_days: lerp(state.interval_days, full.interval_days),
+        ease_factor: lerp(state.ease_factor, full.ease_factor),
+        stability: lerp_opt(state.stability, full.stability),
+        difficulty: lerp_opt(state.difficulty, full.difficulty),
+        next_review: state.next_review + Duration::seconds(shift as i64),
+        ..state.clone()
+    }
+}
+
+/// Running totals for a matching session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct MatchScore {
+    pub pairs: usize,
+    pub mistakes: u32,
+    pub secs: f32,
+}
+
+impl MatchScore {
+    /// Add a finished round.
+    pub fn add_round(&mut self, round: &MatchingRound, secs: f32) {
+        self.pairs += round.cards.len();
+        self.mistakes += round.total_mistakes();
+        self.secs += secs;
+    }
+
+    /// 100 points a pair, less 25 a mistake, plus 10 for every second
+    /// under `PAR_SECS_PER_PAIR` a pair. Never below zero.
+    pub fn points(&self) -> u32 {
+        let time_bonus = (self.pairs as f32 * PAR_SECS_PER_PAIR - self.secs).max(0.0) * 10.0;
+        let points = self.pairs as f32 * 100.0 - self.mistakes as f32 * 25.0 + time_bonus;
+        points.max(0.0).round() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::srs::{FuzzedScheduler, SchedulerKind};
+    use chrono::TimeZone;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 5, 1, 9, 0, 0).unwrap()
+    }
+
+    fn round(cards: &[(&str, &str)]) -> MatchingRound {
+        let cards = cards
+            .iter()
+            .enumerate()
+            .map(|(i, &(t, d))| (i as u64 + 1, Card::new(i as u64 + 1, t, d)))
+            .collect();
+        MatchingRound::new(cards, &mut StdRng::seed_from_u64(1))
+    }
+
+    #[test]
+    fn wrong_pairs_count_against_both_cards() {
+        let mut r = round(&[("chat", "cat"), ("chien", "dog"), ("oiseau", "bird")]);
+
+        assert_eq!(r.select_term(0), None);
+        assert_eq!(r.select_definition(1), Some(PairOutcome::Mismatched));
+        assert_eq!(r.last_mismatch, Some((0, 1)));
+        assert_eq!(r.select_definition(0), None);
+        assert_eq!(r.select_term(0), Some(PairOutcome::Matched));
+        // Matched sides can't be picked again.
+        assert_eq!(r.select_term(0), None);
+        assert!(!r.is_finished());
+
+        r.select_term(1);
+        r.select_definition(1);
+        r.select_definition(2);
+        r.select_term(2);
+        assert!(r.is_finished());
+        assert_eq!(r.total_mistakes(), 1);
+
+        let ratings: Vec<Rating> = r.ratings().map(|(_, rating)| rating).collect();
+        assert_eq!(ratings, [Rating::Hard, Rating::Hard, Rating::Good]);
+    }
+
+    #[test]
+    fn identical_terms_are_interchangeable() {
+        let mut r = round(&[("bank", "river side"), ("bank", "money place"), ("x", "y")]);
+        r.select_term(1);
+        assert_eq!(r.select_definition(0), Some(PairOutcome::Matched));
+        r.select_term(0);
+        assert_eq!(r.select_definition(1), Some(PairOutcome::Matched));
+    }
+
+    /// A card last reviewed 10 days ago, due now.
+    fn graduated(key: u64) -> ReviewState {
+        let mut review = ReviewState::new(key, now());
+        review.phase = CardPhase::Review;
+        review.repetitions = 3;
+        review.interval_days = 10.0;
+        review.last_review = Some(now() - Duration::days(10));
+        review
+    }
+
+    #[test]
+    fn signals_only_nudge_graduated_cards() {
+        let scheduler = SchedulerKind::Sm2.build();
+
+        let new = ReviewState::new(1, now());
+        let after = weighted_review(scheduler.as_ref(), &new, Rating::Good, SIGNAL_WEIGHT, now());
+        assert_eq!(after.next_review, new.next_review);
+        assert!(after.is_new());
+
+        let review = graduated(2);
+        let full = scheduler.review(&review, Rating::Good, now());
+        let after = weighted_review(
+            scheduler.as_ref(),
+            &review,
+            Rating::Good,
+            SIGNAL_WEIGHT,
+            now(),
+        );
+        assert!(after.next_review > review.next_review && after.next_review < full.next_review);
+        assert!(after.interval_days > 10.0 && after.interval_days < full.interval_days);
+        assert_eq!(after.last_review, review.last_review);
+        assert_eq!(after.repetitions, review.repetitions);
+    }
+
+    #[test]
+    fn signals_leave_the_seeded_fuzz_alone() {
+        let review = graduated(1);
+        let fuzzed = FuzzedScheduler::seeded(SchedulerKind::Sm2.build(), Some(7));
+        let untouched = FuzzedScheduler::seeded(SchedulerKind::Sm2.build(), Some(7));
+
+        for _ in 0..3 {
+            weighted_review(&fuzzed, &review, Rating::Good, SIGNAL_WEIGHT, now());
+        }
+        for _ in 0..5 {
+            assert_eq!(
+                fuzzed.review(&review, Rating::Good, now()).next_review,
+                untouched.review(&review, Rating::Good, now()).next_review
+            );
+        }
+    }
+
+    #[test]
+    fn score_rewards_speed_and_accuracy() {
+        let clean = MatchScore {
+            pairs: 6,
+            mistakes: 0,
+            secs: 20.0,
+        };
+        assert_eq!(clean.points(), 600 + 100);
+
+        let sloppy = MatchScore {
+            mistakes: 4,
+            secs: 60.0,
+            ..clean
+        };
+        assert_eq!(sloppy.points(), 500);
+    }
+}
@@ -20,6 +20,7 @@
 // }
 //
 // MflashCard {
+//     id: Option<u64>,
 //     term: String,
 //     definition: String,
 //     term_lang: Option<String>,
@@ -31,18 +32,27 @@
 //     notes: Option<String>,
 // }
 
+use std::collections::HashSet;
 use std::{fs, path::Path};
 
 use serde::{Deserialize, Serialize};
 
 use crate::model::{Card, Deck};
 
+use super::cloze;
+
 /// Language code for cards/decks (e.g. "en", "fr", "ja-JP", "zh-CN").
 pub type LangCode = String;
 
 /// A single card in a `.mflash` deck.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MflashCard {
+    /// Card id, so progress stays with the card when others are added,
+    /// removed or reordered (a cloze note's cards count up from it where
+    /// those ids are free, see `card_ids`). Cards without one get new ids.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+
     /// Prompt side (Unicode).
     pub term: String,
 
@@ -68,6 +78,10 @@ pub struct MflashCard {
     /// Example sentences or usage notes.
     #[serde(default)]
     pub examples: Vec<String>,
+
+    /// Free-form notes about the card.
+    #[serde(default)]
+    pub notes: Option<String>,
 }
 
 /// Top-level `.mflash` deck object.
@@ -121,23 +135,31 @@ impl MflashDeck {
 }
 
 /// Convert an in-memory `Deck` into an `.mflash` deck payload.
-///
-/// Note: the current `Deck` / `Card` model does not track language metadata,
-/// hyperlinks, examples, etc., so those fields are left empty/None.
 impl From<&Deck> for MflashDeck {
     fn from(deck: &Deck) -> Self {
+        // A cloze note is written once (with its first card) and expands
+        // back into one card per deletion on load.
         let cards = deck
             .cards
             .iter()
+            .filter(|c| {
+                c.cloze.is_none() || c.cloze == cloze::indices(&c.definition).first().copied()
+            })
             .map(|c| MflashCard {
-                term: c.term.clone(),
+                id: Some(c.id),
+                term: if c.cloze.is_some() {
+                    cloze::reveal(&c.definition)
+                } else {
+                    c.term.clone()
+                },
                 definition: c.definition.clone(),
-                term_lang: None,
-                def_lang: None,
-                hyperlink: None,
-                media: None,
-                tags: Vec::new(),
-                examples: Vec::new(),
+                term_lang: c.term_lang.clone(),
+                def_lang: c.def_lang.clone(),
+                hyperlink: c.hyperlink.clone(),
+                media: c.media.clone(),
+                tags: c.tags.clone(),
+                examples: c.examples.clone(),
+                notes: c.notes.clone(),
             })
             .collect();
 
@@ -158,27 +180,118 @@ impl From<&Deck> for MflashDeck {
 
 /// Convert an `.mflash` deck back into the in-memory `Deck` type.
 ///
-/// Extra metadata (languages, tags, examples, notes, media, etc.) is currently
-/// not represented in `Deck` and is therefore ignored on import.
+/// The term and definition languages (falling back to the deck defaults),
+/// tags, media, examples, notes and hyperlink are kept, and cloze notes
+/// become one card per deletion (see `srs::cloze`).
+///
+/// Card ids are assigned by `card_ids`.
 impl From<MflashDeck> for Deck {
     fn from(m: MflashDeck) -> Self {
-        let cards = m
+        let notes: Vec<Option<&String>> = m
             .cards
-            .into_iter()
-            .enumerate()
-            .map(|(i, c)| Card {
-                id: (i as u64) + 1,
-                term: c.term,
-                definition: c.definition,
+            .iter()
+            .map(|c| {
+                // Cloze text on either side makes the card a cloze note.
+                [&c.definition, &c.term]
+                    .into_iter()
+                    .find(|text| cloze::is_cloze(text))
             })
             .collect();
+        let ids = card_ids(m.cards.iter().zip(&notes).map(|(c, note)| {
+            let count = note.map_or(1, |note| cloze::indices(note).len() as u64);
+            (c.id, count)
+        }));
+
+        let mut cards: Vec<Card> = Vec::with_capacity(m.cards.len());
+        for ((c, note), ids) in m.cards.iter().zip(notes).zip(ids) {
+            let term_lang = m.effective_term_lang(c).map(str::to_string);
+            let def_lang = m.effective_def_lang(c).map(str::to_string);
+            // Everything but the text itself is shared by a note's cards.
+            // Builder decks write empty strings for unset fields.
+            let with_metadata = |card: Card| Card {
+                term_lang: term_lang.clone(),
+                def_lang: def_lang.clone(),
+                tags: c.tags.clone(),
+                media: c.media.clone(),
+                examples: c.examples.clone(),
+                notes: c.notes.clone().filter(|n| !n.trim().is_empty()),
+                hyperlink: c.hyperlink.clone().filter(|h| !h.trim().is_empty()),
+                ..card
+            };
+
+            match note {
+                Some(note) => cards.extend(cloze::cards(note, ids).into_iter().map(with_metadata)),
+                None => cards.push(with_metadata(Card::new(
+                    ids[0],
+                    c.term.clone(),
+                    c.definition.clone(),
+                ))),
+            }
+        }
 
-        Deck {
-            name: m.title,
-            description: m.description,
-            cards,
+        Deck::new(m.title, m.description, cards)
+    }
+}
+
+/// Card ids of each of `notes`, given as (saved id, number of cards): a
+/// cloze note has one card per deletion. A saved id is never renumbered:
+/// they are all reserved first, and a note's other cards take the ids
+/// after it where those are free, else ids made from the note's own id
+/// (see `cloze::displaced_id`). Either way they only depend on the note,
+/// so adding or removing other cards never moves a deletion's progress.
+/// Notes without an id (or repeating one already used) get fresh ids
+/// above the highest one used. The deck builder numbers its cards with
+/// this too, so they keep the ids the saved deck gives them.
+pub fn card_ids(notes: impl IntoIterator<Item = (Option<u64>, u64)>) -> Vec<Vec<u64>> {
+    let notes: Vec<(Option<u64>, u64)> = notes.into_iter().collect();
+    let mut used: HashSet<u64> = HashSet::new();
+    let saved: Vec<Option<u64>> = notes
+        .iter()
+        .map(|&(id, _)| id.filter(|&id| id > 0 && used.insert(id)))
+        .collect();
+    let mut ids: Vec<Vec<Option<u64>>> = notes
+        .iter()
+        .zip(saved)
+        .map(|(&(_, count), first)| match first {
+            Some(first) => (0..count)
+                .map(|k| {
+                    if k == 0 {
+                        return Some(first);
+                    }
+                    [Some(first + k), cloze::displaced_id(first, k)]
+                        .into_iter()
+                        .flatten()
+                        .find(|id| used.insert(*id))
+                })
+                .collect(),
+            None => vec![None; count as usize],
+        })
+        .collect();
+
+    // Deletions that found no id of their own (a note id too large to
+    // displace) come first, so a new note can't take an id one of them
+    // had before; then new notes, each as one run.
+    let mut fresh_id = used
+        .iter()
+        .filter(|&&id| !cloze::is_displaced(id))
+        .max()
+        .map_or(1, |max| max + 1);
+    let mut next_fresh = || {
+        fresh_id += 1;
+        Some(fresh_id - 1)
+    };
+    for note in ids.iter_mut().filter(|note| note[0].is_some()) {
+        for id in note.iter_mut().filter(|id| id.is_none()) {
+            *id = next_fresh();
         }
     }
+    for note in ids.iter_mut().filter(|note| note[0].is_none()) {
+        note.iter_mut().for_each(|id| *id = next_fresh());
+    }
+
+    ids.into_iter()
+        .map(|note| note.into_iter().flatten().collect())
+        .collect()
 }
 
 /// Save a `Deck` as a `.mflash` file (JSON payload).
@@ -213,3 +326,170 @@ pub fn load_mflash_deck(path: &Path) -> anyhow::Result<Deck> {
 
     Ok(payload.into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(term: &str, definition: &str) -> MflashCard {
+        MflashCard {
+            id: None,
+            term: term.to_string(),
+            definition: definition.to_string(),
+            term_lang: None,
+            def_lang: None,
+            hyperlink: None,
+            media: None,
+            tags: Vec::new(),
+            examples: Vec::new(),
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn card_details_survive_a_round_trip() {
+        let deck = MflashDeck {
+            format: "mflash".to_string(),
+            version: 1,
+            title: "French".to_string(),
+            description: None,
+            snippet: None,
+            default_term_lang: Some("fr".to_string()),
+            default_def_lang: Some("en".to_string()),
+            deck_tags: Vec::new(),
+            cover_media: None,
+            cards: vec![
+                MflashCard {
+                    hyperlink: Some("https://fr.wiktionary.org/wiki/chat".to_string()),
+                    media: Some("media/chat.png".to_string()),
+                    examples: vec!["Le chat dort.".to_string()],
+                    notes: Some("Masculine.".to_string()),
+                    ..card("chat", "cat")
+                },
+                // The deck builder writes empty strings for unset fields.
+                MflashCard {
+                    hyperlink: Some(String::new()),
+                    notes: Some(" ".to_string()),
+                    ..card("{{c1::Paris}} est en {{c2::France}}.", "")
+                },
+            ],
+        };
+
+        let loaded = Deck::from(deck);
+        assert_eq!(loaded.cards.len(), 3);
+        let chat = &loaded.cards[0];
+        assert_eq!(chat.term_lang.as_deref(), Some("fr"));
+        assert_eq!(chat.def_lang.as_deref(), Some("en"));
+        assert_eq!(chat.media.as_deref(), Some("media/chat.png"));
+        assert_eq!(chat.examples, ["Le chat dort."]);
+        assert_eq!(chat.notes.as_deref(), Some("Masculine."));
+        assert!(chat.has_details());
+        assert!(loaded.cards[1..].iter().all(|c| !c.has_details()));
+
+        let saved = MflashDeck::from(&loaded);
+        assert_eq!(saved.cards.len(), 2);
+        assert_eq!(saved.cards[0].hyperlink, chat.hyperlink);
+        assert_eq!(saved.cards[0].notes.as_deref(), Some("Masculine."));
+    }
+
+    #[test]
+    fn card_ids_survive_removing_a_card() {
+        let deck = Deck::new(
+            "French",
+            None,
+            vec![
+                Card::new(1, "chat", "cat"),
+                Card::new(2, "chien", "dog"),
+                Card::new(3, "oiseau", "bird"),
+            ],
+        );
+        let mut saved = MflashDeck::from(&deck);
+        saved.cards.remove(0);
+        // A card added by hand, without an id.
+        saved.cards.push(card("poisson", "fish"));
+
+        let loaded = Deck::from(saved);
+        let ids: Vec<(u64, &str)> = loaded
+            .cards
+            .iter()
+            .map(|c| (c.id, c.term.as_str()))
+            .collect();
+        assert_eq!(ids, [(2, "chien"), (3, "oiseau"), (4, "poisson")]);
+    }
+
+    #[test]
+    fn taken_deletion_keeps_its_id_when_a_card_is_added() {
+        let deletion = |ids: &[Vec<u64>]| ids[0][1];
+        let before = card_ids([(Some(1), 2), (Some(2), 1)]);
+
+        // Added in the builder (no id yet), then saved with the id it got.
+        let added = card_ids([(Some(1), 2), (Some(2), 1), (None, 1)]);
+        assert_eq!(deletion(&added), deletion(&before));
+        assert_eq!(added[2], [3]);
+        let saved = card_ids([(Some(1), 2), (Some(2), 1), (Some(3), 1)]);
+        assert_eq!(deletion(&saved), deletion(&before));
+
+        // Nor does removing one move it.
+        assert_eq!(
+            deletion(&card_ids([(Some(1), 2), (Some(2), 1), (Some(5), 1)])),
+            deletion(&before)
+        );
+    }
+
+    #[test]
+    fn cards_without_ids_get_ids_after_the_saved_ones() {
+        let saved = |id, term: &str, definition: &str| MflashCard {
+            id: Some(id),
+            ..card(term, definition)
+        };
+        let deck = MflashDeck {
+            format: "mflash".to_string(),
+            version: 1,
+            title: "French".to_string(),
+            description: None,
+            snippet: None,
+            default_term_lang: None,
+            default_def_lang: None,
+            deck_tags: Vec::new(),
+            cover_media: None,
+            cards: vec![
+                card("chat", "cat"),
+                saved(1, "chien", "dog"),
+                saved(2, "oiseau", "bird"),
+                card("{{c1::Paris}} est en {{c2::France}}.", ""),
+            ],
+        };
+
+        let loaded = Deck::from(deck);
+        let ids: Vec<u64> = loaded.cards.iter().map(|c| c.id).collect();
+        // The saved cards keep their ids wherever the new ones are.
+        assert_eq!(ids, [3, 1, 2, 4, 5]);
+    }
+
+    #[test]
+    fn cloze_note_keeps_its_id_when_the_next_ones_are_taken() {
+        let displaced = |k| cloze::displaced_id(1, k).unwrap();
+        let ids = card_ids([(Some(1), 3), (Some(2), 1), (None, 1), (Some(3), 1)]);
+        assert_eq!(
+            ids,
+            [
+                vec![1, displaced(1), displaced(2)],
+                vec![2],
+                vec![4],
+                vec![3]
+            ]
+        );
+
+        // Once the new card is saved, nothing moves.
+        let ids = card_ids([(Some(1), 3), (Some(2), 1), (Some(4), 1), (Some(3), 1)]);
+        assert_eq!(
+            ids,
+            [
+                vec![1, displaced(1), displaced(2)],
+                vec![2],
+                vec![4],
+                vec![3]
+            ]
+        );
+    }
+}
@@ -1,29 +1,43 @@
 // src/srs/mod.rs
 //
 // SRS helpers + .mflash support.
-// For now, the scheduling functions are very simple stubs so that
-// the app compiles; we can refine the algorithm later.
-
-use chrono::{DateTime, Utc};
-
-use crate::model::ReviewState;
+//
+// Scheduling goes through the `Scheduler` trait (see `scheduler.rs`);
+// the algorithms themselves live in `sm2.rs` and `fsrs.rs`.
 
+pub mod choice;
+pub mod cloze;
+pub mod day;
+pub mod forecast;
+pub mod fsrs;
+pub mod fuzz;
+pub mod leech;
+pub mod limits;
+pub mod log;
+pub mod matching;
 pub mod mflash;
+pub mod optimizer;
+pub mod progress;
+pub mod quiz;
+pub mod queue;
+pub mod rating;
+pub mod scheduler;
+pub mod session;
+pub mod settings;
+pub mod sm2;
+pub mod steps;
+pub mod text;
+pub mod typed;
 
-/// Very simple placeholder: treat every card as "due".
-pub fn is_due(_state: &ReviewState, _now: DateTime<Utc>) -> bool {
-    true
-}
-
-/// Placeholder SRS update: take the old state and a rating,
-/// and just return the state unchanged for now.
-///
-/// We make this generic over the rating type so it works whether
-/// `rating` is an `i32`, `u8`, etc.
-pub fn update_review_state<T>(state: ReviewState, _rating: T, _now: DateTime<Utc>) -> ReviewState
-where
-    T: Copy + Into<i32>,
-{
-    // TODO: later, actually change the state based on rating & time.
-    state
-}
+pub use choice::{ChoiceBuilder, DistractorStrategy};
+pub use forecast::ForecastDay;
+pub use fuzz::FuzzedScheduler;
+pub use leech::LeechSettings;
+pub use limits::{DailyCounts, DailyLimits};
+pub use log::ReviewLogEntry;
+pub use queue::SessionQueue;
+pub use rating::{AutoGrade, Rating};
+pub use scheduler::{FsrsScheduler, Scheduler, SchedulerKind, SchedulerParams, Sm2Scheduler};
+pub use settings::DeckSrsSettings;
+pub use steps::{LearningSteps, SteppedScheduler};
+pub use typed::{TypedGrader, TypedGrading, TypedVerdict};
@@ -0,0 +1,378 @@
+// src/srs/optimizer.rs
+//
+// Offline parameter fitting from the review log.
+//
+// Both fitters replay the learner's history (see `log.rs`), predict the
+// probability of recall before every review, and tune the parameters to
+// minimise log loss against what actually happened (pass = anything but
+// "Again"). Everything runs locally on the history passed in.
+//
+// - FSRS: all 17 weights, by a bounded pattern search.
+// - SM-2: the ease modifier. SM-2 has no memory model of its own, so a
+//   review is assumed to sit at 90% recall exactly when it falls due, and
+//   the fit finds how much longer (or shorter) intervals could be.
+
+use std::collections::HashMap;
+
+use super::fsrs::{self, FsrsParams};
+use super::log::{ReviewLogEntry, ReviewSource};
+use super::sm2::Sm2Params;
+use crate::model::{CardPhase, Direction};
+
+/// Reviews closer together than this (learning steps) are skipped: the
+/// memory models only describe day-scale forgetting.
+const MIN_ELAPSED_DAYS: f64 = 1.0;
+
+/// Recall assumed when an SM-2 card is reviewed exactly on time.
+const SM2_TARGET_RECALL: f64 = 0.9;
+
+/// Allowed range for each FSRS weight while fitting.
+const FSRS_BOUNDS: [(f64, f64); 17] = [
+    (0.1, 100.0),
+    (0.1, 100.0),
+    (0.1, 100.0),
+    (0.1, 100.0),
+    (1.0, 10.0),
+    (0.001, 4.0),
+    (0.001, 4.0),
+    (0.001, 0.75),
+    (0.0, 4.5),
+    (0.0, 0.8),
+    (0.001, 3.5),
+    (0.001, 5.0),
+    (0.001, 0.25),
+    (0.001, 0.9),
+    (0.0, 4.0),
+    (0.0, 1.0),
+    (1.0, 6.0),
+];
+
+/// Pattern-search rounds before giving up on further improvement.
+const MAX_ROUNDS: usize = 200;
+
+/// Outcome of a fit.
+#[derive(Debug, Clone)]
+pub struct FitReport<P> {
+    /// The fitted parameters.
+    pub params: P,
+    /// Reviews the fit was based on.
+    pub reviews: usize,
+    /// Share of those reviews that were actually recalled.
+    pub actual_retention: f64,
+    /// Mean predicted recall with the starting parameters.
+    pub retention_before: f64,
+    /// Mean predicted recall with the fitted parameters.
+    pub retention_after: f64,
+    pub log_loss_before: f64,
+    pub log_loss_after: f64,
+}
+
+/// One predicted review: probability of recall, and whether it happened.
+type Prediction = (f64, bool);
+
+/// Fit FSRS weights to `history`, starting from `start`. Returns `None`
+/// if the history has no reviews the model can learn from.
+pub fn fit_fsrs(history: &[ReviewLogEntry], start: &FsrsParams) -> Option<FitReport<FsrsParams>> {
+    let cards = card_histories(history);
+    let before = fsrs_predictions(&cards, start);
+    if before.is_empty() {
+        return None;
+    }
+
+    let mut best = start.clone();
+    let mut best_loss = log_loss(&before);
+    let mut step = 0.2;
+
+    for _ in 0..MAX_ROUNDS {
+        let mut improved = false;
+
+        for (i, &(lo, hi)) in FSRS_BOUNDS.iter().enumerate() {
+            for dir in [1.0, -1.0] {
+                let mut candidate = best.clone();
+                let w = candidate.weights[i];
+                candidate.weights[i] = (w + dir * step * w.abs().max(0.1)).clamp(lo, hi);
+                if candidate.weights[i] == w {
+                    continue;
+                }
+
+                let loss = log_loss(&fsrs_predictions(&cards, &candidate));
+                if loss < best_loss {
+                    best = candidate;
+                    best_loss = loss;
+                    improved = true;
+                }
+            }
+        }
+
+        if !improved {
+            step /= 2.0;
+            if step < 1e-3 {
+                break;
+            }
+        }
+    }
+
+    let after = fsrs_predictions(&cards, &best);
+    Some(report(best, &before, &after))
+}
+
+/// Fit the SM-2 ease modifier to `history`, starting from `start`.
+/// Returns `None` if the history has no graduated reviews.
+pub fn fit_sm2(history: &[ReviewLogEntry], start: Sm2Params) -> Option<FitReport<Sm2Params>> {
+    let before = sm2_predictions(history, 1.0);
+    if before.is_empty() {
+        return None;
+    }
+
+    // Golden-section search for the memory-strength factor `k` (on a log
+    // scale): recall is 90% after `k` times the scheduled interval.
+    let loss_at = |ln_k: f64| log_loss(&sm2_predictions(history, ln_k.exp()));
+    let inv_phi = (5f64.sqrt() - 1.0) / 2.0;
+    let (mut a, mut b) = (0.1f64.ln(), 10f64.ln());
+    let mut c = b - inv_phi * (b - a);
+    let mut d = a + inv_phi * (b - a);
+    let (mut fc, mut fd) = (loss_at(c), loss_at(d));
+    while b - a > 1e-4 {
+        if fc < fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = b - inv_phi * (b - a);
+            fc = loss_at(c);
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = a + inv_phi * (b - a);
+            fd = loss_at(d);
+        }
+    }
+
+    // Intervals can grow by `k` and still land on the target recall.
+    let ease_modifier = (start.ease_modifier * ((a + b) / 2.0).exp()).clamp(0.5, 2.5);
+    let k = ease_modifier / start.ease_modifier;
+    let after = sm2_predictions(history, k);
+
+    let params = Sm2Params {
+        ease_modifier,
+        ..start
+    };
+    Some(report(params, &before, &after))
+}
+
+/// Group the log per card (deck + id + direction: each direction is its
+/// own memory), each in time order. Only real reviews count.
+fn card_histories(history: &[ReviewLogEntry]) -> Vec<Vec<&ReviewLogEntry>> {
+    let mut by_card: HashMap<(&str, u64, Direction), Vec<&ReviewLogEntry>> = HashMap::new();
+    for entry in history.iter().filter(|e| e.source == ReviewSource::Study) {
+        by_card
+            .entry((entry.deck.as_str(), entry.card_id, entry.direction))
+            .or_default()
+            .push(entry);
+    }
+
+    let mut cards: Vec<_> = by_card.into_iter().collect();
+    // Deterministic order, so fits are reproducible.
+    cards.sort_by_key(|(key, _)| *key);
+    cards
+        .into_iter()
+        .map(|(_, mut entries)| {
+            entries.sort_by_key(|e| e.timestamp);
+            entries
+        })
+        .collect()
+}
+
+/// Replay every card through FSRS and predict each day-scale review.
+fn fsrs_predictions(cards: &[Vec<&ReviewLogEntry>], params: &FsrsParams) -> Vec<Prediction> {
+    let mut out = Vec::new();
+
+    for entries in cards {
+        let mut memory = None;
+        let mut last = None;
+
+        for entry in entries {
+            let grade = entry.rating.fsrs_grade();
+            let Some(last_time) = last else {
+                memory = Some(params.next_memory(None, 0.0, grade));
+                last = Some(entry.timestamp);
+                continue;
+            };
+
+            let elapsed = (entry.timestamp - last_time).num_seconds() as f64 / 86_400.0;
+            if elapsed < MIN_ELAPSED_DAYS {
+                continue;
+            }
+
+            if let Some((s, _)) = memory {
+                out.push((fsrs::retrievability(elapsed, s), entry.rating.is_pass()));
+            }
+            memory = Some(params.next_memory(memory, elapsed, grade));
+            last = Some(entry.timestamp);
+        }
+    }
+
+    out
+}
+
+/// Predict each graduated SM-2 review, assuming recall falls to the
+/// target after `k` times the interval the card was scheduled with.
+fn sm2_predictions(history: &[ReviewLogEntry], k: f64) -> Vec<Prediction> {
+    history
+        .iter()
+        .filter(|e| e.source == ReviewSource::Study)
+        .filter(|e| e.phase == CardPhase::Review && e.interval_before >= 1.0)
+        .filter_map(|e| {
+            let elapsed = e.elapsed_days.filter(|&d| d >= MIN_ELAPSED_DAYS)?;
+            let p = SM2_TARGET_RECALL.powf(elapsed / (k * e.interval_before));
+            Some((p, e.rating.is_pass()))
+        })
+        .collect()
+}
+
+fn report<P>(params: P, before: &[Prediction], after: &[Prediction]) -> FitReport<P> {
+    let passed = before.iter().filter(|(_, pass)| *pass).count();
+    FitReport {
+        params,
+        reviews: before.len(),
+        actual_retention: passed as f64 / before.len() as f64,
+        retention_before: mean_recall(before),
+        retention_after: mean_recall(after),
+        log_loss_before: log_loss(before),
+        log_loss_after: log_loss(after),
+    }
+}
+
+fn mean_recall(predictions: &[Prediction]) -> f64 {
+    predictions.iter().map(|(p, _)| p).sum::<f64>() / predictions.len().max(1) as f64
+}
+
+/// Mean binary cross-entropy of the predictions.
+fn log_loss(predictions: &[Prediction]) -> f64 {
+    let total: f64 = predictions
+        .iter()
+        .map(|&(p, pass)| {
+            let p = p.clamp(1e-4, 1.0 - 1e-4);
+            if pass {
+                -p.ln()
+            } else {
+                -(1.0 - p).ln()
+            }
+        })
+        .sum();
+    total / predictions.len().max(1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ReviewState;
+    use crate::srs::{Rating, SchedulerKind, SchedulerParams};
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+
+    fn t0() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 1, 9, 0, 0).unwrap()
+    }
+
+    fn entry(card_id: u64, day: i64, rating: Rating, interval_before: f64) -> ReviewLogEntry {
+        ReviewLogEntry {
+            card_id,
+            direction: Direction::Reverse,
+            deck: "test".to_string(),
+            timestamp: t0() + Duration::days(day),
+            rating,
+            chosen: None,
+            response_secs: 2.0,
+            phase: if interval_before > 0.0 {
+                CardPhase::Review
+            } else {
+                CardPhase::New
+            },
+            elapsed_days: (interval_before > 0.0).then_some(interval_before),
+            interval_before,
+            interval_after: 0.0,
+            ease_before: 2.5,
+            ease_after: 2.5,
+            source: ReviewSource::Study,
+        }
+    }
+
+    /// Cards reviewed on days 0, 1, 7, 23, 63: recalled every time, except
+    /// every third card forgets at the last review.
+    fn history() -> Vec<ReviewLogEntry> {
+        let days = [0, 1, 7, 23, 63];
+        let mut out = Vec::new();
+        for card in 1..=30 {
+            for (i, &day) in days.iter().enumerate() {
+                let interval = if i == 0 {
+                    0.0
+                } else {
+                    (day - days[i - 1]) as f64
+                };
+                let forgot = card % 3 == 0 && i == days.len() - 1;
+                let rating = if forgot { Rating::Again } else { Rating::Good };
+                out.push(entry(card, day, rating, interval));
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn fsrs_fit_does_not_increase_loss() {
+        let report = fit_fsrs(&history(), &FsrsParams::default()).unwrap();
+        assert_eq!(report.reviews, 120);
+        assert!((report.actual_retention - 110.0 / 120.0).abs() < 1e-9);
+        assert!(report.log_loss_after <= report.log_loss_before);
+    }
+
+    #[test]
+    fn sm2_fit_lengthens_intervals_when_recall_is_high() {
+        let report = fit_sm2(&history(), Sm2Params::default()).unwrap();
+        assert!(report.params.ease_modifier > 1.0);
+        assert!(report.log_loss_after < report.log_loss_before);
+        assert!(report.retention_after > report.retention_before);
+    }
+
+    /// Interval after answering a new card "Good" three times, each time
+    /// when due.
+    fn third_interval(kind: SchedulerKind, params: &SchedulerParams) -> f64 {
+        let scheduler = kind.build_with_retention(0.9, params);
+        let mut state = ReviewState::new(1, t0());
+        for _ in 0..3 {
+            state = scheduler.review(&state, Rating::Good, state.next_review);
+        }
+        state.interval_days
+    }
+
+    #[test]
+    fn fitted_params_change_the_scheduled_intervals() {
+        let default = SchedulerParams::default();
+
+        let fsrs = fit_fsrs(&history(), &FsrsParams::default()).unwrap();
+        let fitted = SchedulerParams {
+            fsrs_weights: Some(fsrs.params.weights),
+            ..default
+        };
+        assert_ne!(
+            third_interval(SchedulerKind::Fsrs, &fitted),
+            third_interval(SchedulerKind::Fsrs, &default)
+        );
+
+        // Recall was high, so SM-2 spaces reviews out further.
+        let sm2 = fit_sm2(&history(), Sm2Params::default()).unwrap();
+        let fitted = SchedulerParams {
+            sm2_ease_modifier: Some(sm2.params.ease_modifier),
+            ..default
+        };
+        assert!(
+            third_interval(SchedulerKind::Sm2, &fitted)
+                > third_interval(SchedulerKind::Sm2, &default)
+        );
+    }
+
+    #[test]
+    fn empty_history_has_nothing_to_fit() {
+        assert!(fit_fsrs(&[], &FsrsParams::default()).is_none());
+        assert!(fit_sm2(&[], Sm2Params::default()).is_none());
+    }
+}
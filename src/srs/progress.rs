@@ -0,0 +1,259 @@
+// src/srs/progress.rs
+//
+// Per-deck review progress on disk.
+//
+// Each deck's `ReviewState`s are stored as a JSON object keyed by review
+// key (card id + direction, see `Direction::key`) in
+// `progress/<deck>-progress.json`, where `<deck>` is the deck file's
+// stem (so `decks/animals.json` ↔ `progress/animals-progress.json`).
+// Today's new/review counts live beside it in `<deck>-today.json`.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+
+use crate::model::{Card, CardPhase, Direction, ReviewState};
+
+use super::limits::DailyCounts;
+
+/// Default directory for progress files (relative to the working dir).
+pub const PROGRESS_DIR: &str = "progress";
+
+/// Progress file for the deck whose file stem is `deck_key`.
+pub fn progress_path(dir: &Path, deck_key: &str) -> PathBuf {
+    dir.join(format!("{deck_key}-progress.json"))
+}
+
+/// Daily-counts file for the deck whose file stem is `deck_key`.
+pub fn counts_path(dir: &Path, deck_key: &str) -> PathBuf {
+    dir.join(format!("{deck_key}-today.json"))
+}
+
+/// Load saved progress. A missing file simply means "no progress yet".
+pub fn load_progress(path: &Path) -> anyhow::Result<HashMap<u64, ReviewState>> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let data = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read progress file {}", path.display()))?;
+    let states = serde_json::from_str(&data)
+        .with_context(|| format!("Failed to parse progress file {}", path.display()))?;
+    Ok(states)
+}
+
+/// Save progress atomically: write to a temp file in the same directory,
+/// then rename it over the old file, so a crash mid-write never leaves a
+/// truncated progress file behind.
+pub fn save_progress(path: &Path, states: &HashMap<u64, ReviewState>) -> anyhow::Result<()> {
+    write_atomic(path, &serde_json::to_vec_pretty(states)?)
+}
+
+/// Load today's counts. A missing file means nothing was studied yet.
+pub fn load_counts(path: &Path) -> anyhow::Result<DailyCounts> {
+    if !path.exists() {
+        return Ok(DailyCounts::default());
+    }
+
+    let data = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read counts file {}", path.display()))?;
+    let counts = serde_json::from_str(&data)
+        .with_context(|| format!("Failed to parse counts file {}", path.display()))?;
+    Ok(counts)
+}
+
+/// Save today's counts (atomically, like `save_progress`).
+pub fn save_counts(path: &Path, counts: &DailyCounts) -> anyhow::Result<()> {
+    write_atomic(path, &serde_json::to_vec_pretty(counts)?)
+}
+
+pub(crate) fn write_atomic(path: &Path, data: &[u8]) -> anyhow::Result<()> {
+    let dir = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create progress dir {}", dir.display()))?;
+
+    let mut tmp = tempfile::NamedTempFile::new_in(dir)?;
+    tmp.write_all(data)?;
+    tmp.as_file().sync_all()?;
+    tmp.persist(path)
+        .with_context(|| format!("Failed to replace {}", path.display()))?;
+    Ok(())
+}
+
+/// What `reconcile` changed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReconcileReport {
+    /// Cards with no saved state (new to the deck) that were initialized.
+    pub added: usize,
+    /// Saved states whose card no longer exists in the deck and were dropped.
+    pub removed: usize,
+}
+
+/// Bring saved progress in line with the deck's current cards:
+/// - every card gets a fresh `ReviewState` due at `now` for each of
+///   `directions` it is asked in (see `Card::directions`) and has none
+///   for yet,
+/// - states for card ids the deck no longer has are dropped (states of
+///   directions not in `directions` are kept for later),
+/// - states saved by older versions get their `phase` filled in.
+pub fn reconcile(
+    states: &mut HashMap<u64, ReviewState>,
+    cards: &[Card],
+    directions: &[Direction],
+    now: DateTime<Utc>,
+) -> ReconcileReport {
+    let ids: HashSet<u64> = cards.iter().map(|c| c.id).collect();
+
+    let before = states.len();
+    states.retain(|key, _| ids.contains(&Direction::split_key(*key).0));
+    let removed = before - states.len();
+
+    let mut added = 0;
+    for card in cards {
+        for direction in card.directions(directions) {
+            let key = direction.key(card.id);
+            states.entry(key).or_insert_with(|| {
+                added += 1;
+                ReviewState::new(key, now)
+            });
+        }
+    }
+
+    for (id, state) in states.iter_mut() {
+        // Keep the redundant `card_id` field consistent with the map key.
+        state.card_id = *id;
+
+        // Progress saved before phases existed: answered cards are reviews.
+        let answered = state.repetitions > 0 || state.last_review.is_some();
+        if state.phase == CardPhase::New && answered {
+            state.phase = CardPhase::Review;
+        }
+    }
+
+    ReconcileReport { added, removed }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone};
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 5, 1, 9, 30, 0).unwrap()
+    }
+
+    fn card(id: u64) -> Card {
+        Card {
+            id,
+            term: format!("term {id}"),
+            definition: format!("definition {id}"),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn progress_and_counts_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = progress_path(dir.path(), "animals");
+        assert!(load_progress(&path).unwrap().is_empty());
+
+        let mut state = ReviewState::new(7, now());
+        state.interval_days = 3.0;
+        state.repetitions = 2;
+        state.phase = CardPhase::Review;
+        state.last_review = Some(now());
+        state.tags.push("leech".to_string());
+        let states = HashMap::from([(7, state)]);
+        save_progress(&path, &states).unwrap();
+
+        let back = load_progress(&path).unwrap();
+        let state = &back[&7];
+        assert_eq!(state.card_id, 7);
+        assert_eq!(state.interval_days, 3.0);
+        assert_eq!(state.repetitions, 2);
+        assert_eq!(state.phase, CardPhase::Review);
+        assert_eq!(state.last_review, Some(now()));
+        assert_eq!(state.tags, ["leech"]);
+
+        let path = counts_path(dir.path(), "animals");
+        assert_eq!(load_counts(&path).unwrap(), DailyCounts::default());
+        let counts = DailyCounts {
+            day: NaiveDate::from_ymd_opt(2025, 5, 1),
+            new_studied: 4,
+            reviews_done: 12,
+        };
+        save_counts(&path, &counts).unwrap();
+        assert_eq!(load_counts(&path).unwrap(), counts);
+    }
+
+    #[test]
+    fn reconcile_adds_new_cards_and_drops_removed_ones() {
+        let mut states: HashMap<u64, ReviewState> = [1, 2, Direction::Forward.key(2), 3]
+            .into_iter()
+            .map(|key| (key, ReviewState::new(key, now())))
+            .collect();
+        states.get_mut(&1).unwrap().interval_days = 5.0;
+
+        // Card 3 was deleted and card 4 added; only the reverse is studied.
+        let cards = [card(1), card(2), card(4)];
+        let report = reconcile(&mut states, &cards, &[Direction::Reverse], now());
+        assert_eq!(
+            report,
+            ReconcileReport {
+                added: 1,
+                removed: 1
+            }
+        );
+
+        let mut keys: Vec<u64> = states.keys().copied().collect();
+        keys.sort_unstable();
+        assert_eq!(keys, [1, 2, 4, Direction::Forward.key(2)]);
+        assert_eq!(states[&1].interval_days, 5.0);
+        assert_eq!(states[&4].next_review, now());
+
+        // Studying both ways adds the missing forward states.
+        let report = reconcile(&mut states, &cards, &Direction::ALL, now());
+        assert_eq!(
+            report,
+            ReconcileReport {
+                added: 2,
+                removed: 0
+            }
+        );
+        assert_eq!(states.len(), 6);
+    }
+
+    #[test]
+    fn reconcile_backfills_phases_of_old_progress() {
+        // Saved before phases existed: no `phase` key at all.
+        let json = r#"{
+            "1": { "card_id": 1, "interval_days": 6.0, "ease_factor": 2.5,
+                   "repetitions": 2, "next_review": "2025-05-05T09:30:00Z" },
+            "2": { "card_id": 2, "interval_days": 0.0, "ease_factor": 2.5,
+                   "repetitions": 0, "next_review": "2025-05-01T09:30:00Z" }
+        }"#;
+        let dir = tempfile::tempdir().unwrap();
+        let path = progress_path(dir.path(), "old");
+        fs::write(&path, json).unwrap();
+
+        let mut states = load_progress(&path).unwrap();
+        assert!(states.values().all(|s| s.phase == CardPhase::New));
+
+        let report = reconcile(
+            &mut states,
+            &[card(1), card(2)],
+            &[Direction::Reverse],
+            now(),
+        );
+        assert_eq!(report, ReconcileReport::default());
+        assert_eq!(states[&1].phase, CardPhase::Review);
+        assert_eq!(states[&2].phase, CardPhase::New);
+    }
+}
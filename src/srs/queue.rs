@@ -0,0 +1,584 @@
+// src/srs/queue.rs
+//
+// Session queue: decides which cards a study session shows, and in what
+// order, from the deck's `ReviewState`s.
+//
+// - Up to `review_limit` review cards whose `next_review` has passed are
+//   included, most overdue first. Learning and relearning cards left
+//   from an earlier session count as reviews too (`limits::is_review`).
+// - Up to `new_limit` never-reviewed cards are mixed in (deck order),
+//   spread evenly between the reviews.
+// - Cards answered during the session that land in a learning or
+//   relearning step come back once that step is due (or a little early,
+//   within `LEARN_AHEAD`, when nothing else is left).
+// - With "learn until correct", a missed card instead comes back a few
+//   cards later (`reinsert`), so the session only ends once every card
+//   has been answered correctly.
+// - Suspended cards are never shown; buried ones wait for the next day.
+// - Each direction of a card is queued on its own (see `Direction::key`);
+//   with `separate_siblings` only one direction per card makes it into
+//   the session (the most overdue, else the first new one).
+// - Everything else waits; `next_due` reports when the next one is up.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::model::{Direction, ReviewState};
+
+use super::{limits, Scheduler};
+
+/// How far ahead a learning card may be shown early once nothing else
+/// is left in the session.
+pub const LEARN_AHEAD: Duration = Duration::minutes(20);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionQueue {
+    pending: VecDeque<u64>,
+
+    /// Cards in learning / relearning steps, with the time each is due.
+    learning: Vec<(DateTime<Utc>, u64)>,
+
+    /// Number of cards shown (or to be shown) this session, counting
+    /// every repeat of a learning card.
+    pub total: usize,
+
+    /// How many of those are new (never reviewed) cards.
+    pub new_count: usize,
+
+    /// How many of those are due reviews.
+    pub review_count: usize,
+
+    /// Cards answered at least once this session ("seen"), and those
+    /// answered correctly at least once ("mastered").
+    seen: HashSet<u64>,
+    mastered: HashSet<u64>,
+}
+
+impl SessionQueue {
+    /// Build the queue for a session starting at `now` from the review
+    /// keys to study, in deck order.
+    pub fn build(
+        keys: &[u64],
+        states: &HashMap<u64, ReviewState>,
+        scheduler: &dyn Scheduler,
+        now: DateTime<Utc>,
+        new_limit: usize,
+        review_limit: usize,
+        separate_siblings: bool,
+    ) -> Self {
+        let mut reviews: Vec<(&ReviewState, u64)> = Vec::new();
+        let mut new_cards: Vec<u64> = Vec::new();
+
+        for &key in keys {
+            match states.get(&key) {
+                None => new_cards.push(key),
+                Some(state) if state.suspended || state.is_buried(now) => {}
+                Some(state) if !limits::is_review(state) => new_cards.push(key),
+                Some(state) if scheduler.is_due(state, now) => reviews.push((state, key)),
+                Some(_) => {}
+            }
+        }
+
+        // Most overdue first (earliest `next_review`); ties keep deck order.
+        reviews.sort_by_key(|(state, _)| state.next_review);
+
+        if separate_siblings {
+            let mut cards_taken = HashSet::new();
+            let mut first_of_card = |key: u64| cards_taken.insert(Direction::split_key(key).0);
+            reviews.retain(|&(_, key)| first_of_card(key));
+            new_cards.retain(|&key| first_of_card(key));
+        }
+
+        let reviews: Vec<u64> = reviews
+            .into_iter()
+            .take(review_limit)
+            .map(|(_, id)| id)
+            .collect();
+
+        new_cards.truncate(new_limit);
+
+        let pending = interleave(&reviews, &new_cards);
+
+        Self {
+            total: pending.len(),
+            new_count: new_cards.len(),
+            review_count: reviews.len(),
+            pending,
+            learning: Vec::new(),
+            seen: HashSet::new(),
+            mastered: HashSet::new(),
+        }
+    }
+
+    /// Take the next card to show at `now`:
+    /// 1. a learning card whose step is due,
+    /// 2. otherwise the next queued review / new card,
+    /// 3. otherwise a learning card due within `LEARN_AHEAD`.
+    pub fn next(&mut self, now: DateTime<Utc>) -> Option<u64> {
+        if let Some(id) = self.take_learning(now) {
+            return Some(id);
+        }
+        if let Some(id) = self.pending.pop_front() {
+            return Some(id);
+        }
+        self.take_learning(now + LEARN_AHEAD)
+    }
+
+    /// Put a just-answered card back into the session if its new state is
+    /// a learning step due before the end of the day (`day_end`).
+    /// Returns `true` if the card was requeued.
+    pub fn requeue_if_learning(&mut self, state: &ReviewState, day_end: DateTime<Utc>) -> bool {
+        if !state.is_learning() || state.suspended || state.next_review >= day_end {
+            return false;
+        }
+
+        self.learning.retain(|(_, id)| *id != state.card_id);
+        self.learning.push((state.next_review, state.card_id));
+        self.total += 1;
+        true
+    }
+
+    /// Put a missed card back `gap` cards from now (learn until correct),
+    /// in place of any learning repeat it was waiting for.
+    pub fn reinsert(&mut self, card_id: u64, gap: usize) {
+        let before = self.remaining();
+        self.pending.retain(|id| *id != card_id);
+        self.learning.retain(|(_, id)| *id != card_id);
+        let dropped = before - self.remaining();
+
+        let at = gap.min(self.pending.len());
+        self.pending.insert(at, card_id);
+        self.total = self.total + 1 - dropped;
+    }
+
+    /// Put cards that were taken but never answered (e.g. the one showing
+    /// when the session was saved) back at the front, in order.
+    pub fn put_back(&mut self, keys: &[u64]) {
+        for &key in keys.iter().rev() {
+            self.pending.push_front(key);
+        }
+    }
+
+    /// Count an answer to `card_id` towards "seen" and, if it was
+    /// correct, "mastered".
+    pub fn record_answer(&mut self, card_id: u64, correct: bool) {
+        self.seen.insert(card_id);
+        if correct {
+            self.mastered.insert(card_id);
+        }
+    }
+
+    /// Whether `card_id` was already answered this session.
+    pub fn is_seen(&self, card_id: u64) -> bool {
+        self.seen.contains(&card_id)
+    }
+
+    /// Distinct cards answered this session.
+    pub fn seen_count(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Distinct cards answered correctly this session.
+    pub fn mastered_count(&self) -> usize {
+        self.mastered.len()
+    }
+
+    /// Distinct cards in the session: answered ones plus those still
+    /// waiting.
+    pub fn card_count(&self) -> usize {
+        let waiting = self
+            .pending
+            .iter()
+            .chain(self.learning.iter().map(|(_, id)| id))
+            .filter(|id| !self.seen.contains(id));
+        self.seen.len() + waiting.collect::<HashSet<_>>().len()
+    }
+
+    /// Drop `card_id` from the rest of the session (e.g. it was just
+    /// suspended or buried).
+    pub fn remove(&mut self, card_id: u64) {
+        let before = self.remaining();
+        self.pending.retain(|id| *id != card_id);
+        self.learning.retain(|(_, id)| *id != card_id);
+        self.total -= before - self.remaining();
+        self.seen.remove(&card_id);
+        self.mastered.remove(&card_id);
+    }
+
+    /// Take the card showing (`card_id`) out of the rest of the session
+    /// after it was suspended or buried. Once answered it still counts
+    /// towards the total; unanswered, it drops out.
+    pub fn skip(&mut self, card_id: u64, answered: bool) {
+        self.remove(card_id);
+        if !answered {
+            self.total = self.total.saturating_sub(1);
+        }
+    }
+
+    /// Cards still waiting to be shown (including learning repeats).
+    pub fn remaining(&self) -> usize {
+        self.pending.len() + self.learning.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty() && self.learning.is_empty()
+    }
+
+    /// Earliest learning card due at or before `limit`.
+    fn take_learning(&mut self, limit: DateTime<Utc>) -> Option<u64> {
+        let (idx, _) = self
+            .learning
+            .iter()
+            .enumerate()
+            .filter(|(_, (due, _))| *due <= limit)
+            .min_by_key(|(_, (due, _))| *due)?;
+        Some(self.learning.remove(idx).1)
+    }
+}
+
+/// Spread `new_cards` evenly through `reviews`, starting with a review.
+fn interleave(reviews: &[u64], new_cards: &[u64]) -> VecDeque<u64> {
+    let (r, n) = (reviews.len(), new_cards.len());
+    let mut out = VecDeque::with_capacity(r + n);
+    let (mut ri, mut ni) = (0, 0);
+
+    while ri < r || ni < n {
+        // Take a new card when its share so far lags the reviews' share.
+        let take_new = ni < n && (ri >= r || ni * r < ri * n);
+        if take_new {
+            out.push_back(new_cards[ni]);
+            ni += 1;
+        } else {
+            out.push_back(reviews[ri]);
+            ri += 1;
+        }
+    }
+
+    out
+}
+
+/// When the next already-reviewed card falls due (`None` if every card
+/// is still new or suspended).
+pub fn next_due(states: &HashMap<u64, ReviewState>) -> Option<DateTime<Utc>> {
+    states
+        .values()
+        .filter(|s| !s.is_new() && !s.suspended)
+        .map(|s| s.buried_until.map_or(s.next_review, |b| b.max(s.next_review)))
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{CardFlag, CardPhase};
+    use crate::srs::{DailyCounts, DailyLimits, Rating, SchedulerKind};
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 10, 12, 0, 0).unwrap()
+    }
+
+    /// Both directions of cards 1..=n, all new.
+    fn both_directions(n: u64) -> (Vec<u64>, HashMap<u64, ReviewState>) {
+        let keys: Vec<u64> = (1..=n)
+            .flat_map(|id| Direction::ALL.map(|d| d.key(id)))
+            .collect();
+        let states = keys
+            .iter()
+            .map(|&key| (key, ReviewState::new(key, now())))
+            .collect();
+        (keys, states)
+    }
+
+    /// A review card that fell due `days` ago (not yet due if negative).
+    fn review(key: u64, days: i64) -> (u64, ReviewState) {
+        let mut state = ReviewState::new(key, now());
+        state.phase = CardPhase::Review;
+        state.repetitions = 2;
+        state.interval_days = 3.0;
+        state.last_review = Some(now() - Duration::days(days + 3));
+        state.next_review = now() - Duration::days(days);
+        (key, state)
+    }
+
+    /// Reviews 1..=4 due 1, 7, 3 and 5 days ago, 5 not due yet, 6 and 7 new.
+    fn mixed_deck() -> (Vec<u64>, HashMap<u64, ReviewState>) {
+        let keys: Vec<u64> = (1..=7).collect();
+        let mut states: HashMap<u64, ReviewState> = [
+            review(1, 1),
+            review(2, 7),
+            review(3, 3),
+            review(4, 5),
+            review(5, -2),
+        ]
+        .into_iter()
+        .collect();
+        states.insert(6, ReviewState::new(6, now()));
+        (keys, states)
+    }
+
+    #[test]
+    fn most_overdue_reviews_first_with_new_cards_spread_between() {
+        let (keys, states) = mixed_deck();
+        let scheduler = SchedulerKind::Sm2.build();
+        let mut queue =
+            SessionQueue::build(&keys, &states, scheduler.as_ref(), now(), 10, 10, false);
+
+        assert_eq!(
+            (queue.new_count, queue.review_count, queue.total),
+            (2, 4, 6)
+        );
+        let shown: Vec<u64> = std::iter::from_fn(|| queue.next(now())).collect();
+        assert_eq!(shown, [2, 6, 4, 3, 7, 1]);
+    }
+
+    #[test]
+    fn limits_cap_new_cards_and_keep_the_most_overdue_reviews() {
+        let (keys, states) = mixed_deck();
+        let scheduler = SchedulerKind::Sm2.build();
+        let mut queue = SessionQueue::build(&keys, &states, scheduler.as_ref(), now(), 1, 2, false);
+
+        assert_eq!(
+            (queue.new_count, queue.review_count, queue.total),
+            (1, 2, 3)
+        );
+        let shown: Vec<u64> = std::iter::from_fn(|| queue.next(now())).collect();
+        assert_eq!(shown, [2, 6, 4]);
+
+        let mut none = SessionQueue::build(&keys, &states, scheduler.as_ref(), now(), 0, 0, false);
+        assert!(none.is_empty());
+        assert_eq!(none.next(now()), None);
+    }
+
+    #[test]
+    fn a_full_session_stays_within_the_daily_review_cap() {
+        let limits = DailyLimits {
+            new_per_day: 10,
+            reviews_per_day: 3,
+        };
+        let mut counts = DailyCounts::default();
+        let keys: Vec<u64> = (1..=5).collect();
+        let mut states: HashMap<u64, ReviewState> =
+            [review(1, 1), review(2, 4), review(3, 2), review(4, 3)]
+                .into_iter()
+                .collect();
+        // Left on a step in an earlier session.
+        states.get_mut(&3).unwrap().phase = CardPhase::Relearning;
+        states.get_mut(&4).unwrap().phase = CardPhase::Learning;
+        states.insert(5, ReviewState::new(5, now()));
+        let scheduler = SchedulerKind::Sm2.build();
+        let build = |states: &HashMap<u64, ReviewState>, counts: &DailyCounts| {
+            SessionQueue::build(
+                &keys,
+                states,
+                scheduler.as_ref(),
+                now(),
+                counts.new_remaining(&limits),
+                counts.reviews_remaining(&limits),
+                false,
+            )
+        };
+
+        let mut queue = build(&states, &counts);
+        assert_eq!((queue.new_count, queue.review_count), (1, 3));
+
+        // Learning until correct, with no relearning steps: the first
+        // review is missed and comes back as a review again.
+        let mut missed = false;
+        while let Some(key) = queue.next(now()) {
+            let state = &states[&key];
+            counts.record(state, queue.is_seen(key));
+            let rating = if missed { Rating::Good } else { Rating::Again };
+            missed = true;
+            let updated = scheduler.review(state, rating, now());
+            queue.record_answer(key, rating.is_pass());
+            if !rating.is_pass() {
+                queue.reinsert(key, 1);
+            }
+            states.insert(key, updated);
+        }
+        assert_eq!(queue.total, 5);
+        assert_eq!((counts.new_studied, counts.reviews_done), (1, 3));
+
+        // The cap is used up: the review left over waits for tomorrow.
+        let rest = build(&states, &counts);
+        assert_eq!((rest.new_count, rest.review_count), (0, 0));
+    }
+
+    #[test]
+    fn learning_cards_come_back_when_due_or_early_once_nothing_else_is_left() {
+        let keys: Vec<u64> = (1..=2).collect();
+        let states = keys
+            .iter()
+            .map(|&key| (key, ReviewState::new(key, now())))
+            .collect();
+        let scheduler = SchedulerKind::Sm2.build();
+        let mut queue =
+            SessionQueue::build(&keys, &states, scheduler.as_ref(), now(), 10, 10, false);
+        let day_end = now() + Duration::hours(12);
+
+        let learning = |key, minutes| {
+            let mut state = ReviewState::new(key, now());
+            state.phase = CardPhase::Learning;
+            state.next_review = now() + Duration::minutes(minutes);
+            state
+        };
+
+        // Card 1 is on a 10 minute step, card 2 on a 30 minute one.
+        assert_eq!(queue.next(now()), Some(1));
+        assert!(queue.requeue_if_learning(&learning(1, 10), day_end));
+        assert_eq!(queue.next(now()), Some(2));
+        assert!(queue.requeue_if_learning(&learning(2, 30), day_end));
+        assert_eq!(queue.total, 4);
+
+        // Nothing else left: card 1 is within LEARN_AHEAD, card 2 isn't.
+        assert_eq!(queue.next(now()), Some(1));
+        assert_eq!(queue.next(now()), None);
+        assert!(!queue.is_empty());
+        assert_eq!(queue.next(now() + Duration::minutes(10)), Some(2));
+
+        // Steps past the end of the day, and graduated cards, wait.
+        assert!(!queue.requeue_if_learning(&learning(1, 13 * 60), day_end));
+        assert!(!queue.requeue_if_learning(&review(2, -1).1, day_end));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn learning_card_due_beats_the_queue() {
+        let keys: Vec<u64> = (1..=3).collect();
+        let states = keys
+            .iter()
+            .map(|&key| (key, ReviewState::new(key, now())))
+            .collect();
+        let scheduler = SchedulerKind::Sm2.build();
+        let mut queue =
+            SessionQueue::build(&keys, &states, scheduler.as_ref(), now(), 10, 10, false);
+
+        assert_eq!(queue.next(now()), Some(1));
+        let mut state = ReviewState::new(1, now());
+        state.phase = CardPhase::Relearning;
+        state.next_review = now() + Duration::minutes(1);
+        assert!(queue.requeue_if_learning(&state, now() + Duration::hours(12)));
+
+        assert_eq!(queue.next(now()), Some(2));
+        assert_eq!(queue.next(now() + Duration::minutes(1)), Some(1));
+        assert_eq!(queue.next(now() + Duration::minutes(1)), Some(3));
+    }
+
+    #[test]
+    fn directions_are_queued_separately_unless_siblings_are_separated() {
+        let (keys, states) = both_directions(3);
+        let scheduler = SchedulerKind::Sm2.build();
+
+        let both = SessionQueue::build(&keys, &states, scheduler.as_ref(), now(), 10, 10, false);
+        assert_eq!(both.total, 6);
+
+        let mut one = SessionQueue::build(&keys, &states, scheduler.as_ref(), now(), 10, 10, true);
+        assert_eq!(one.total, 3);
+        let mut cards: Vec<u64> = std::iter::from_fn(|| one.next(now()))
+            .map(|key| Direction::split_key(key).0)
+            .collect();
+        cards.sort();
+        assert_eq!(cards, [1, 2, 3]);
+    }
+
+    #[test]
+    fn the_more_overdue_sibling_is_kept() {
+        let (keys, mut states) = both_directions(1);
+        for (days, direction) in [(1, Direction::Forward), (5, Direction::Reverse)] {
+            let state = states.get_mut(&direction.key(1)).unwrap();
+            state.repetitions = 2;
+            state.last_review = Some(now() - Duration::days(days + 1));
+            state.next_review = now() - Duration::days(days);
+        }
+        let scheduler = SchedulerKind::Sm2.build();
+
+        let mut queue =
+            SessionQueue::build(&keys, &states, scheduler.as_ref(), now(), 10, 10, true);
+        assert_eq!(queue.next(now()), Some(Direction::Reverse.key(1)));
+        assert_eq!(queue.next(now()), None);
+    }
+
+    #[test]
+    fn missed_cards_come_back_until_answered_correctly() {
+        let keys: Vec<u64> = (1..=4).collect();
+        let states = keys
+            .iter()
+            .map(|&key| (key, ReviewState::new(key, now())))
+            .collect();
+        let scheduler = SchedulerKind::Sm2.build();
+        let mut queue =
+            SessionQueue::build(&keys, &states, scheduler.as_ref(), now(), 10, 10, false);
+
+        // Miss card 1 twice, answer everything else right first time.
+        let mut shown = Vec::new();
+        let mut misses = 2;
+        while let Some(key) = queue.next(now()) {
+            shown.push(key);
+            let correct = !(key == 1 && misses > 0);
+            queue.record_answer(key, correct);
+            if !correct {
+                misses -= 1;
+                queue.reinsert(key, 2);
+            }
+            assert_eq!(queue.card_count(), 4);
+        }
+
+        assert_eq!(shown, [1, 2, 3, 1, 4, 1]);
+        assert_eq!(queue.total, 6);
+        assert_eq!((queue.seen_count(), queue.mastered_count()), (4, 4));
+    }
+
+    #[test]
+    fn suspended_and_buried_cards_sit_out_flagged_ones_do_not() {
+        let keys: Vec<u64> = (1..=4).collect();
+        let mut states: HashMap<u64, ReviewState> = keys
+            .iter()
+            .map(|&key| (key, ReviewState::new(key, now())))
+            .collect();
+        let tomorrow = now() + Duration::hours(12);
+        states.get_mut(&1).unwrap().suspended = true;
+        states.get_mut(&2).unwrap().buried_until = Some(tomorrow);
+        states.get_mut(&3).unwrap().flag = Some(CardFlag::Red);
+        let scheduler = SchedulerKind::Sm2.build();
+
+        let mut today =
+            SessionQueue::build(&keys, &states, scheduler.as_ref(), now(), 10, 10, false);
+        let shown: Vec<u64> = std::iter::from_fn(|| today.next(now())).collect();
+        assert_eq!(shown, [3, 4]);
+
+        let mut next_day =
+            SessionQueue::build(&keys, &states, scheduler.as_ref(), tomorrow, 10, 10, false);
+        let shown: Vec<u64> = std::iter::from_fn(|| next_day.next(tomorrow)).collect();
+        assert_eq!(shown, [2, 3, 4]);
+    }
+
+    #[test]
+    fn skipping_the_card_showing_only_drops_it_from_the_total_if_unanswered() {
+        let keys: Vec<u64> = (1..=3).collect();
+        let states = keys
+            .iter()
+            .map(|&key| (key, ReviewState::new(key, now())))
+            .collect();
+        let scheduler = SchedulerKind::Sm2.build();
+        let mut queue =
+            SessionQueue::build(&keys, &states, scheduler.as_ref(), now(), 10, 10, false);
+
+        // Suspended before answering.
+        let first = queue.next(now()).unwrap();
+        queue.skip(first, false);
+        assert_eq!((queue.total, queue.remaining()), (2, 2));
+
+        // Buried after answering (and requeued as a learning repeat).
+        let second = queue.next(now()).unwrap();
+        queue.record_answer(second, false);
+        queue.reinsert(second, 1);
+        assert_eq!(queue.total, 3);
+        queue.skip(second, true);
+        assert_eq!((queue.total, queue.remaining()), (2, 1));
+        assert_eq!(queue.next(now()), Some(3));
+        assert_eq!(queue.next(now()), None);
+    }
+}
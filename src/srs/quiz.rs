@@ -0,0 +1,360 @@
+// src/srs/quiz.rs
+//
+// Sprint and exam quizzes: runs through a deck that never touch review
+// state.
+//
+// - Sprint: as many cards as possible before the time runs out (the deck
+//   is reshuffled if it runs out first).
+// - Exam: a fixed number of random cards, each asked once, with nothing
+//   said about the answers until the end.
+//
+// Every answer is kept for the results report, which can be saved as
+// JSON or CSV.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::model::Direction;
+
+/// Which kind of quiz.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuizKind {
+    Sprint,
+    Exam,
+}
+
+impl QuizKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            QuizKind::Sprint => "Sprint",
+            QuizKind::Exam => "Exam",
+        }
+    }
+}
+
+/// One answered question.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuizAnswer {
+    /// 1-based position in the quiz.
+    pub number: usize,
+    pub card_id: u64,
+    pub direction: Direction,
+    /// What was asked, and the answer expected.
+    pub prompt: String,
+    pub expected: String,
+    /// What the learner picked or typed.
+    pub given: String,
+    pub correct: bool,
+    pub secs: f32,
+}
+
+/// A quiz in progress.
+#[derive(Debug, Clone)]
+pub struct Quiz {
+    pub kind: QuizKind,
+    /// Deck key (the deck file's stem).
+    pub deck: String,
+    pub started_at: DateTime<Utc>,
+    /// Sprint: how long the run lasts.
+    pub time_limit_secs: Option<u32>,
+    /// Questions in the quiz (an exam's length; unknown for a sprint).
+    pub length: Option<usize>,
+    pub answers: Vec<QuizAnswer>,
+    /// Review keys the quiz draws from, and those still to come (taken
+    /// from the back).
+    keys: Vec<u64>,
+    pending: Vec<u64>,
+}
+
+impl Quiz {
+    /// A sprint over `keys` lasting `secs` seconds.
+    pub fn sprint(
+        deck: &str,
+        keys: Vec<u64>,
+        secs: u32,
+        now: DateTime<Utc>,
+        rng: &mut dyn RngCore,
+    ) -> Self {
+        let mut pending = keys.clone();
+        pending.shuffle(rng);
+        Self {
+            kind: QuizKind::Sprint,
+            deck: deck.to_string(),
+            started_at: now,
+            time_limit_secs: Some(secs),
+            length: None,
+            answers: Vec::new(),
+            keys,
+            pending,
+        }
+    }
+
+    /// An exam of `questions` random cards from `keys` (all of them if
+    /// there are fewer).
+    pub fn exam(
+        deck: &str,
+        mut keys: Vec<u64>,
+        questions: usize,
+        now: DateTime<Utc>,
+        rng: &mut dyn RngCore,
+    ) -> Self {
+        keys.shuffle(rng);
+        keys.truncate(questions);
+        Self {
+            kind: QuizKind::Exam,
+            deck: deck.to_string(),
+            started_at: now,
+            time_limit_secs: None,
+            length: Some(keys.len()),
+            answers: Vec::new(),
+            pending: keys.clone(),
+            keys,
+        }
+    }
+
+    /// The next card to ask, or `None` once an exam has asked every
+    /// question. A sprint reshuffles the deck when it runs out, without
+    /// asking the same card twice in a row.
+    pub fn next(&mut self, rng: &mut dyn RngCore) -> Option<u64> {
+        if self.pending.is_empty() && self.kind == QuizKind::Sprint {
+            let last = self.answers.last().map(|a| a.direction.key(a.card_id));
+            self.pending = self.keys.clone();
+            self.pending.shuffle(rng);
+            if self.pending.len() > 1 && self.pending.last().copied() == last {
+                self.pending.swap(0, self.keys.len() - 1);
+            }
+        }
+        self.pending.pop()
+    }
+
+    /// Seconds left in a sprint (`None` for an exam).
+    pub fn remaining_secs(&self, now: DateTime<Utc>) -> Option<f32> {
+        let limit = self.time_limit_secs?;
+        let elapsed = (now - self.started_at).num_milliseconds() as f32 / 1000.0;
+        Some((limit as f32 - elapsed).max(0.0))
+    }
+
+    /// Whether a sprint's time is up.
+    pub fn is_time_up(&self, now: DateTime<Utc>) -> bool {
+        self.remaining_secs(now).is_some_and(|secs| secs <= 0.0)
+    }
+
+    pub fn correct_count(&self) -> usize {
+        self.answers.iter().filter(|a| a.correct).count()
+    }
+
+    /// Record an answer to the card direction `key`.
+    pub fn record(
+        &mut self,
+        key: u64,
+        prompt: &str,
+        expected: &str,
+        given: &str,
+        correct: bool,
+        secs: f32,
+    ) {
+        let (card_id, direction) = Direction::split_key(key);
+        self.answers.push(QuizAnswer {
+            number: self.answers.len() + 1,
+            card_id,
+            direction,
+            prompt: prompt.to_string(),
+            expected: expected.to_string(),
+            given: given.to_string(),
+            correct,
+            secs,
+        });
+    }
+
+    /// The results, as of `now`.
+    pub fn report(&self, now: DateTime<Utc>) -> QuizReport {
+        QuizReport {
+            kind: self.kind,
+            deck: self.deck.clone(),
+            started_at: self.started_at,
+            finished_at: now,
+            questions: self.answers.len(),
+            correct: self.correct_count(),
+            answers: self.answers.clone(),
+        }
+    }
+}
+
+/// Results of a finished quiz.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuizReport {
+    pub kind: QuizKind,
+    pub deck: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub questions: usize,
+    pub correct: usize,
+    pub answers: Vec<QuizAnswer>,
+}
+
+impl QuizReport {
+    /// Share of questions answered correctly, in percent.
+    pub fn score_percent(&self) -> f32 {
+        if self.questions == 0 {
+            0.0
+        } else {
+            self.correct as f32 / self.questions as f32 * 100.0
+        }
+    }
+
+    pub fn duration_secs(&self) -> f32 {
+        (self.finished_at - self.started_at).num_milliseconds() as f32 / 1000.0
+    }
+
+    /// Default file name for saving the report, e.g.
+    /// `animals-exam-2025-05-01-0930`.
+    pub fn file_stem(&self) -> String {
+        format!(
+            "{}-{}-{}",
+            self.deck,
+            self.kind.label().to_lowercase(),
+            self.started_at.format("%Y-%m-%d-%H%M")
+        )
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// One row per question, with a header row.
+    pub fn to_csv(&self) -> anyhow::Result<String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record([
+            "number",
+            "card_id",
+            "direction",
+            "prompt",
+            "expected",
+            "given",
+            "correct",
+            "secs",
+        ])?;
+        for a in &self.answers {
+            let direction = match a.direction {
+                Direction::Forward => "forward",
+                Direction::Reverse => "reverse",
+            };
+            writer.write_record([
+                a.number.to_string(),
+                a.card_id.to_string(),
+                direction.to_string(),
+                a.prompt.clone(),
+                a.expected.clone(),
+                a.given.clone(),
+                a.correct.to_string(),
+                format!("{:.2}", a.secs),
+            ])?;
+        }
+        Ok(String::from_utf8(writer.into_inner()?)?)
+    }
+}
+
+/// Save `report` to `path`: CSV if the extension is `.csv`, else JSON.
+pub fn save_report(path: &Path, report: &QuizReport) -> anyhow::Result<()> {
+    let is_csv = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+    let text = if is_csv {
+        report.to_csv()?
+    } else {
+        report.to_json()?
+    };
+    fs::write(path, text).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 5, 1, 9, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn exam_asks_each_card_once() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut quiz = Quiz::exam("animals", (1..=10).collect(), 4, now(), &mut rng);
+        assert_eq!(quiz.length, Some(4));
+
+        let mut asked = Vec::new();
+        while let Some(key) = quiz.next(&mut rng) {
+            asked.push(key);
+        }
+        asked.sort_unstable();
+        asked.dedup();
+        assert_eq!(asked.len(), 4);
+        assert!(quiz.remaining_secs(now()).is_none());
+    }
+
+    #[test]
+    fn sprint_reshuffles_until_time_is_up() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut quiz = Quiz::sprint("animals", vec![1, 2, 3], 60, now(), &mut rng);
+
+        let mut last = None;
+        for _ in 0..10 {
+            let key = quiz.next(&mut rng).unwrap();
+            assert_ne!(Some(key), last);
+            quiz.record(key, "q", "a", "a", true, 1.0);
+            last = Some(key);
+        }
+        assert_eq!(quiz.answers.len(), 10);
+
+        assert_eq!(
+            quiz.remaining_secs(now() + Duration::seconds(45)),
+            Some(15.0)
+        );
+        assert!(!quiz.is_time_up(now() + Duration::seconds(59)));
+        assert!(quiz.is_time_up(now() + Duration::seconds(60)));
+    }
+
+    #[test]
+    fn report_exports_every_answer() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut quiz = Quiz::exam("animals", vec![1, 2], 2, now(), &mut rng);
+        quiz.record(1, "cat", "chat", "chat", true, 2.5);
+        quiz.record(
+            Direction::Forward.key(2),
+            "chien",
+            "dog",
+            "cat, dog",
+            false,
+            4.0,
+        );
+
+        let report = quiz.report(now() + Duration::seconds(30));
+        assert_eq!((report.correct, report.questions), (1, 2));
+        assert_eq!(report.score_percent(), 50.0);
+        assert_eq!(report.duration_secs(), 30.0);
+        assert_eq!(report.file_stem(), "animals-exam-2025-05-01-0930");
+
+        let csv = report.to_csv().unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "number,card_id,direction,prompt,expected,given,correct,secs",
+                "1,1,reverse,cat,chat,chat,true,2.50",
+                "2,2,forward,chien,dog,\"cat, dog\",false,4.00",
+            ]
+        );
+
+        let back: QuizReport = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(back, report);
+    }
+}
@@ -0,0 +1,115 @@
+// src/srs/rating.rs
+//
+// How well the learner knew a card. Every scheduler takes one of these
+// four grades (the same buttons Anki and FSRS use).
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Rating {
+    /// Forgot the card (a lapse).
+    Again,
+    /// Recalled, but with serious difficulty.
+    Hard,
+    /// Recalled after a little thought.
+    Good,
+    /// Recalled instantly.
+    Easy,
+}
+
+impl Rating {
+    pub const ALL: [Rating; 4] = [Rating::Again, Rating::Hard, Rating::Good, Rating::Easy];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Rating::Again => "Again",
+            Rating::Hard => "Hard",
+            Rating::Good => "Good",
+            Rating::Easy => "Easy",
+        }
+    }
+
+    /// Whether the card counts as remembered.
+    pub fn is_pass(self) -> bool {
+        self != Rating::Again
+    }
+
+    /// Equivalent SM-2 quality grade (0..=5).
+    pub fn sm2_quality(self) -> i32 {
+        match self {
+            Rating::Again => 1,
+            Rating::Hard => 3,
+            Rating::Good => 4,
+            Rating::Easy => 5,
+        }
+    }
+
+    /// Equivalent FSRS grade (1..=4).
+    pub fn fsrs_grade(self) -> u8 {
+        match self {
+            Rating::Again => 1,
+            Rating::Hard => 2,
+            Rating::Good => 3,
+            Rating::Easy => 4,
+        }
+    }
+}
+
+/// Derives a rating from a multiple-choice answer: wrong answers are
+/// `Again`; right answers are graded by how long the learner took.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AutoGrade {
+    /// Correct answers at or under this many seconds are `Easy`.
+    pub fast_secs: f32,
+    /// Correct answers over this many seconds are `Hard`; anything in
+    /// between is `Good`.
+    pub slow_secs: f32,
+}
+
+impl Default for AutoGrade {
+    fn default() -> Self {
+        Self {
+            fast_secs: 3.0,
+            slow_secs: 10.0,
+        }
+    }
+}
+
+impl AutoGrade {
+    pub fn rate(&self, correct: bool, response_secs: f32) -> Rating {
+        if !correct {
+            Rating::Again
+        } else if response_secs <= self.fast_secs {
+            Rating::Easy
+        } else if response_secs <= self.slow_secs {
+            Rating::Good
+        } else {
+            Rating::Hard
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_grade_boundaries() {
+        let grade = AutoGrade::default();
+        assert_eq!(grade.rate(true, 0.0), Rating::Easy);
+        assert_eq!(grade.rate(true, 3.0), Rating::Easy);
+        assert_eq!(grade.rate(true, 3.01), Rating::Good);
+        assert_eq!(grade.rate(true, 10.0), Rating::Good);
+        assert_eq!(grade.rate(true, 10.01), Rating::Hard);
+        assert_eq!(grade.rate(true, 600.0), Rating::Hard);
+    }
+
+    #[test]
+    fn wrong_answers_are_again_however_fast() {
+        let grade = AutoGrade::default();
+        for secs in [0.0, 3.0, 10.0, 600.0] {
+            assert_eq!(grade.rate(false, secs), Rating::Again);
+        }
+    }
+}
@@ -0,0 +1,183 @@
+// src/srs/scheduler.rs
+//
+// Pluggable scheduling: every algorithm implements `Scheduler`, and
+// callers (the GUI, headless tools) only ever talk to the trait.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::model::ReviewState;
+
+use super::{fsrs, sm2, Rating};
+
+/// A spaced-repetition algorithm.
+pub trait Scheduler {
+    /// Which algorithm this is.
+    fn kind(&self) -> SchedulerKind;
+
+    /// Whether the card should be shown at `now`.
+    fn is_due(&self, state: &ReviewState, now: DateTime<Utc>) -> bool {
+        state.next_review <= now
+    }
+
+    /// Apply one review and return the updated state.
+    fn review(&self, state: &ReviewState, rating: Rating, now: DateTime<Utc>) -> ReviewState;
+
+    /// What `review` would return, for showing ahead of time: no side
+    /// effects, so nothing random (such as interval fuzz) is drawn.
+    fn preview(&self, state: &ReviewState, rating: Rating, now: DateTime<Utc>) -> ReviewState {
+        self.review(state, rating, now)
+    }
+}
+
+/// The scheduling algorithms MorFlash ships with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SchedulerKind {
+    /// Classic SuperMemo-2 (ease factor + growing intervals).
+    #[default]
+    Sm2,
+    /// FSRS-4.5 (stability / difficulty memory model).
+    Fsrs,
+}
+
+impl SchedulerKind {
+    pub const ALL: [SchedulerKind; 2] = [SchedulerKind::Sm2, SchedulerKind::Fsrs];
+
+    /// Human-readable name for options screens.
+    pub fn label(self) -> &'static str {
+        match self {
+            SchedulerKind::Sm2 => "SM-2 (classic)",
+            SchedulerKind::Fsrs => "FSRS",
+        }
+    }
+
+    /// Construct the scheduler with its default parameters.
+    pub fn build(self) -> Box<dyn Scheduler> {
+        self.build_with_retention(fsrs::DEFAULT_DESIRED_RETENTION, &SchedulerParams::default())
+    }
+
+    /// Construct the scheduler aiming for `desired_retention` (0.0..1.0),
+    /// with any fitted parameters in `params` in place of the defaults.
+    pub fn build_with_retention(
+        self,
+        desired_retention: f64,
+        params: &SchedulerParams,
+    ) -> Box<dyn Scheduler> {
+        let sm2_defaults = sm2::Sm2Params::default();
+        let fsrs_defaults = fsrs::FsrsParams::default();
+        match self {
+            SchedulerKind::Sm2 => Box::new(Sm2Scheduler {
+                params: sm2::Sm2Params {
+                    ease_modifier: params
+                        .sm2_ease_modifier
+                        .unwrap_or(sm2_defaults.ease_modifier),
+                    desired_retention,
+                },
+            }),
+            SchedulerKind::Fsrs => Box::new(FsrsScheduler {
+                params: fsrs::FsrsParams {
+                    weights: params.fsrs_weights.unwrap_or(fsrs_defaults.weights),
+                    desired_retention,
+                },
+            }),
+        }
+    }
+}
+
+/// Parameters fitted to the learner's history (see `optimizer`) to use
+/// instead of the defaults. Each only applies to its own algorithm.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SchedulerParams {
+    /// FSRS weights (see `optimizer::fit_fsrs`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fsrs_weights: Option<[f64; 17]>,
+
+    /// SM-2 ease modifier (see `optimizer::fit_sm2`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sm2_ease_modifier: Option<f64>,
+}
+
+/// SM-2 behind the `Scheduler` trait.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sm2Scheduler {
+    pub params: sm2::Sm2Params,
+}
+
+impl Scheduler for Sm2Scheduler {
+    fn kind(&self) -> SchedulerKind {
+        SchedulerKind::Sm2
+    }
+
+    fn review(&self, state: &ReviewState, rating: Rating, now: DateTime<Utc>) -> ReviewState {
+        self.params.schedule(state, rating.sm2_quality(), now)
+    }
+}
+
+/// FSRS behind the `Scheduler` trait.
+#[derive(Debug, Clone, Default)]
+pub struct FsrsScheduler {
+    pub params: fsrs::FsrsParams,
+}
+
+impl Scheduler for FsrsScheduler {
+    fn kind(&self) -> SchedulerKind {
+        SchedulerKind::Fsrs
+    }
+
+    fn review(&self, state: &ReviewState, rating: Rating, now: DateTime<Utc>) -> ReviewState {
+        self.params.schedule(state, rating.fsrs_grade(), now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 5, 1, 9, 30, 0).unwrap()
+    }
+
+    /// Interval after answering a new card "Good" three times, each time
+    /// when due (SM-2's first two intervals are fixed).
+    fn third_interval(scheduler: &dyn Scheduler) -> f64 {
+        let mut state = ReviewState::new(1, now());
+        for _ in 0..3 {
+            state = scheduler.review(&state, Rating::Good, state.next_review);
+        }
+        state.interval_days
+    }
+
+    #[test]
+    fn kinds_build_their_scheduler() {
+        for kind in SchedulerKind::ALL {
+            assert_eq!(kind.build().kind(), kind);
+        }
+    }
+
+    #[test]
+    fn build_with_retention_honours_the_retention() {
+        for kind in SchedulerKind::ALL {
+            let params = SchedulerParams::default();
+            let relaxed = kind.build_with_retention(0.8, &params);
+            let default = kind.build();
+            let strict = kind.build_with_retention(0.97, &params);
+
+            let (relaxed, default, strict) = (
+                third_interval(relaxed.as_ref()),
+                third_interval(default.as_ref()),
+                third_interval(strict.as_ref()),
+            );
+            assert!(relaxed > default, "{kind:?}: {relaxed} <= {default}");
+            assert!(strict < default, "{kind:?}: {strict} >= {default}");
+        }
+
+        // FSRS's first interval is exactly where recall hits the target.
+        let fsrs = SchedulerKind::Fsrs.build_with_retention(0.8, &SchedulerParams::default());
+        let first = fsrs.review(&ReviewState::new(1, now()), Rating::Good, now());
+        let expected = fsrs::interval_for(fsrs::DEFAULT_WEIGHTS[2], 0.8).round();
+        assert_eq!(first.interval_days, expected);
+    }
+}
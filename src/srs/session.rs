@@ -0,0 +1,175 @@
+// src/srs/session.rs
+//
+// Unfinished study sessions on disk, so quitting mid-deck loses nothing.
+//
+// The session being studied is snapshotted to `progress/session.json`
+// after every answer and when the window closes: the deck file and a
+// fingerprint of its contents, the queue in order (learning repeats and
+// the seen / mastered cards included) and the session's counters. Only
+// the last session studied is kept, and it is removed once finished.
+// A snapshot whose deck file has changed since can't be resumed.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::matching::MatchScore;
+use super::progress::write_atomic;
+use super::queue::SessionQueue;
+
+/// Snapshot file (in the progress directory).
+pub fn session_path(dir: &Path) -> PathBuf {
+    dir.join("session.json")
+}
+
+/// Everything needed to pick a study session up where it was left.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    /// The deck file studied, and its `fingerprint` when it was opened.
+    pub deck_path: PathBuf,
+    pub deck_fingerprint: u64,
+    pub saved_at: DateTime<Utc>,
+    /// Cards still to come. A card that was showing but not yet answered
+    /// is put back at the front.
+    pub queue: SessionQueue,
+    pub reviewed_count: usize,
+    pub total_cards: usize,
+    /// Review keys of the cards that became leeches this session.
+    pub leeches: Vec<u64>,
+    pub matching_score: MatchScore,
+}
+
+impl SessionSnapshot {
+    /// Deck key (the deck file's stem), e.g. for the resume button.
+    pub fn deck_key(&self) -> String {
+        self.deck_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    /// Whether the deck file still holds what was being studied.
+    pub fn deck_unchanged(&self) -> anyhow::Result<bool> {
+        let bytes = fs::read(&self.deck_path)
+            .with_context(|| format!("Failed to read deck {}", self.deck_path.display()))?;
+        Ok(fingerprint(&bytes) == self.deck_fingerprint)
+    }
+}
+
+/// Fingerprint of a deck file's contents (64-bit FNV-1a, so it stays the
+/// same across builds).
+pub fn fingerprint(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Load the saved session, if there is one.
+pub fn load(path: &Path) -> anyhow::Result<Option<SessionSnapshot>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let data = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read session file {}", path.display()))?;
+    let snapshot = serde_json::from_str(&data)
+        .with_context(|| format!("Failed to parse session file {}", path.display()))?;
+    Ok(Some(snapshot))
+}
+
+/// Save the session (atomically, like progress files).
+pub fn save(path: &Path, snapshot: &SessionSnapshot) -> anyhow::Result<()> {
+    write_atomic(path, &serde_json::to_vec_pretty(snapshot)?)
+}
+
+/// Forget the saved session (a missing file is fine).
+pub fn clear(path: &Path) -> anyhow::Result<()> {
+    if path.exists() {
+        fs::remove_file(path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ReviewState;
+    use crate::srs::SchedulerKind;
+    use chrono::TimeZone;
+    use std::collections::HashMap;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 5, 1, 9, 30, 0).unwrap()
+    }
+
+    fn snapshot(deck_path: PathBuf, deck: &[u8]) -> SessionSnapshot {
+        let keys: Vec<u64> = (1..=4).collect();
+        let states: HashMap<u64, ReviewState> = keys
+            .iter()
+            .map(|&k| (k, ReviewState::new(k, now())))
+            .collect();
+        let scheduler = SchedulerKind::Sm2.build();
+        let mut queue =
+            SessionQueue::build(&keys, &states, scheduler.as_ref(), now(), 10, 10, false);
+
+        // Card 1 answered, card 2 showing when the session was saved.
+        let first = queue.next(now()).unwrap();
+        queue.record_answer(first, true);
+        let showing = queue.next(now()).unwrap();
+        queue.put_back(&[showing]);
+
+        SessionSnapshot {
+            deck_path,
+            deck_fingerprint: fingerprint(deck),
+            saved_at: now(),
+            queue,
+            reviewed_count: 1,
+            total_cards: 4,
+            leeches: vec![first],
+            matching_score: MatchScore::default(),
+        }
+    }
+
+    #[test]
+    fn snapshot_round_trip_keeps_the_queue_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let deck_path = dir.path().join("animals.json");
+        fs::write(&deck_path, b"{\"cards\": []}").unwrap();
+
+        let path = session_path(dir.path());
+        assert!(load(&path).unwrap().is_none());
+        save(&path, &snapshot(deck_path, b"{\"cards\": []}")).unwrap();
+
+        let mut back = load(&path).unwrap().unwrap();
+        assert_eq!(back.deck_key(), "animals");
+        assert_eq!((back.reviewed_count, back.total_cards), (1, 4));
+        assert_eq!(back.leeches, [1]);
+        assert_eq!(back.queue.seen_count(), 1);
+        assert_eq!(back.queue.mastered_count(), 1);
+        let rest: Vec<u64> = std::iter::from_fn(|| back.queue.next(now())).collect();
+        assert_eq!(rest, [2, 3, 4]);
+
+        clear(&path).unwrap();
+        assert!(load(&path).unwrap().is_none());
+        clear(&path).unwrap();
+    }
+
+    #[test]
+    fn edited_deck_is_not_resumable() {
+        let dir = tempfile::tempdir().unwrap();
+        let deck_path = dir.path().join("animals.json");
+        fs::write(&deck_path, b"{\"cards\": []}").unwrap();
+
+        let snap = snapshot(deck_path.clone(), b"{\"cards\": []}");
+        assert!(snap.deck_unchanged().unwrap());
+
+        fs::write(&deck_path, b"{\"cards\": [1]}").unwrap();
+        assert!(!snap.deck_unchanged().unwrap());
+
+        fs::remove_file(&deck_path).unwrap();
+        assert!(snap.deck_unchanged().is_err());
+    }
+}
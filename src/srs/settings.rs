@@ -0,0 +1,183 @@
+// src/srs/settings.rs
+//
+// Per-deck scheduling overrides. These live in the deck file under an
+// optional `"srs"` key; any field left out falls back to the global
+// study options.
+
+use serde::{Deserialize, Serialize};
+
+use super::leech::LeechSettings;
+use super::limits::DailyLimits;
+use super::scheduler::{SchedulerKind, SchedulerParams};
+use super::steps::LearningSteps;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeckSrsSettings {
+    /// Scheduling algorithm for this deck.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheduler: Option<SchedulerKind>,
+
+    /// Learning steps in minutes for new cards (e.g. `[1, 10, 1440]`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub learning_steps: Option<Vec<u32>>,
+
+    /// Relearning steps in minutes for lapsed cards.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relearning_steps: Option<Vec<u32>>,
+
+    /// Cap on never-studied cards introduced per day.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_per_day: Option<usize>,
+
+    /// Cap on review cards shown per day.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reviews_per_day: Option<usize>,
+
+    /// Lapses after which a card counts as a leech (0 = never).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub leech_threshold: Option<u32>,
+
+    /// Target probability of recalling a card when it comes due (e.g. 0.9).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub desired_retention: Option<f64>,
+
+    /// Fitted FSRS weights for this deck (see `optimizer::fit_fsrs`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fsrs_weights: Option<[f64; 17]>,
+
+    /// Fitted SM-2 ease modifier for this deck (see `optimizer::fit_sm2`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sm2_ease_modifier: Option<f64>,
+}
+
+impl DeckSrsSettings {
+    /// True when the deck doesn't override anything (used to keep deck
+    /// files free of an empty `"srs": {}` block).
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The algorithm to use for this deck, given the global default.
+    pub fn scheduler_or(&self, global: SchedulerKind) -> SchedulerKind {
+        self.scheduler.unwrap_or(global)
+    }
+
+    /// Desired retention for this deck, given the global default.
+    pub fn retention_or(&self, global: f64) -> f64 {
+        self.desired_retention.unwrap_or(global)
+    }
+
+    /// Fitted scheduler parameters for this deck, given the global ones.
+    pub fn params_or(&self, global: &SchedulerParams) -> SchedulerParams {
+        SchedulerParams {
+            fsrs_weights: self.fsrs_weights.or(global.fsrs_weights),
+            sm2_ease_modifier: self.sm2_ease_modifier.or(global.sm2_ease_modifier),
+        }
+    }
+
+    /// Learning / relearning steps for this deck, given the global default.
+    pub fn steps_or(&self, global: &LearningSteps) -> LearningSteps {
+        LearningSteps {
+            learning: self
+                .learning_steps
+                .clone()
+                .unwrap_or_else(|| global.learning.clone()),
+            relearning: self
+                .relearning_steps
+                .clone()
+                .unwrap_or_else(|| global.relearning.clone()),
+        }
+    }
+
+    /// Daily caps for this deck, given the global default.
+    pub fn limits_or(&self, global: DailyLimits) -> DailyLimits {
+        DailyLimits {
+            new_per_day: self.new_per_day.unwrap_or(global.new_per_day),
+            reviews_per_day: self.reviews_per_day.unwrap_or(global.reviews_per_day),
+        }
+    }
+
+    /// Leech handling for this deck, given the global default.
+    pub fn leech_or(&self, global: LeechSettings) -> LeechSettings {
+        LeechSettings {
+            threshold: self.leech_threshold.unwrap_or(global.threshold),
+            ..global
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Deck;
+
+    fn deck(json: &str) -> Deck {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn deck_srs_block_overrides_the_defaults() {
+        let deck = deck(
+            r#"{
+                "name": "Verbs",
+                "cards": [],
+                "srs": {
+                    "scheduler": "fsrs",
+                    "desired_retention": 0.85,
+                    "learning_steps": [5],
+                    "new_per_day": 5,
+                    "leech_threshold": 3,
+                    "sm2_ease_modifier": 1.2
+                }
+            }"#,
+        );
+        let srs = &deck.srs;
+        assert!(!srs.is_empty());
+
+        assert_eq!(srs.scheduler_or(SchedulerKind::Sm2), SchedulerKind::Fsrs);
+        assert_eq!(srs.retention_or(0.9), 0.85);
+
+        let global = SchedulerParams {
+            fsrs_weights: Some([1.0; 17]),
+            sm2_ease_modifier: Some(0.9),
+        };
+        let params = srs.params_or(&global);
+        assert_eq!(params.fsrs_weights, Some([1.0; 17]));
+        assert_eq!(params.sm2_ease_modifier, Some(1.2));
+
+        let steps = srs.steps_or(&LearningSteps::default());
+        assert_eq!(steps.learning, [5]);
+        assert_eq!(steps.relearning, LearningSteps::default().relearning);
+
+        let limits = srs.limits_or(DailyLimits::default());
+        assert_eq!(limits.new_per_day, 5);
+        assert_eq!(limits.reviews_per_day, 200);
+
+        let leech = srs.leech_or(LeechSettings::default());
+        assert_eq!(leech.threshold, 3);
+        assert!(leech.suspend);
+    }
+
+    #[test]
+    fn deck_without_srs_block_uses_the_defaults() {
+        let deck = deck(r#"{ "name": "Verbs", "cards": [] }"#);
+        let srs = &deck.srs;
+        assert!(srs.is_empty());
+
+        assert_eq!(srs.scheduler_or(SchedulerKind::Sm2), SchedulerKind::Sm2);
+        assert_eq!(srs.retention_or(0.9), 0.9);
+        assert_eq!(
+            srs.params_or(&SchedulerParams::default()),
+            SchedulerParams::default()
+        );
+        assert_eq!(srs.steps_or(&LearningSteps::default()).learning, [1, 10]);
+        let limits = srs.limits_or(DailyLimits::default());
+        assert_eq!((limits.new_per_day, limits.reviews_per_day), (20, 200));
+        assert_eq!(srs.leech_or(LeechSettings::default()).threshold, 8);
+
+        // ...and writes none back.
+        let json = serde_json::to_string(&deck).unwrap();
+        assert!(!json.contains("srs"), "{json}");
+    }
+}
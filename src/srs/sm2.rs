@@ -0,0 +1,226 @@
+// src/srs/sm2.rs
+//
+// Classic SuperMemo-2 scheduling.
+//
+// Ratings are SM-2 "quality" grades from 0 to 5:
+//   5 – perfect response
+//   4 – correct after some hesitation
+//   3 – correct with serious difficulty
+//   2 – wrong, but the answer felt familiar
+//   1 – wrong, the answer was vaguely remembered
+//   0 – complete blackout
+//
+// Grades below 3 count as a lapse: the repetition counter resets and the
+// card comes back after one day. Every grade adjusts the ease factor.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::model::ReviewState;
+
+/// Lowest ease factor SM-2 allows; below this, cards would be shown
+/// far too often to be useful.
+pub const MIN_EASE: f64 = 1.3;
+
+/// Interval (in days) after the first successful repetition.
+pub const FIRST_INTERVAL_DAYS: f64 = 1.0;
+
+/// Interval (in days) after the second successful repetition.
+pub const SECOND_INTERVAL_DAYS: f64 = 6.0;
+
+/// Lowest grade that still counts as a successful recall.
+pub const PASSING_QUALITY: i32 = 3;
+
+/// Retention that unmodified SM-2 intervals are assumed to give.
+pub const BASELINE_RETENTION: f64 = 0.9;
+
+/// Tunable SM-2 parameters (see `optimizer::fit_sm2`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sm2Params {
+    /// Multiplies the ease factor when an interval grows: above 1.0
+    /// spaces reviews out faster, below 1.0 brings them back sooner.
+    pub ease_modifier: f64,
+
+    /// Target probability of recall. Classic SM-2 intervals correspond to
+    /// roughly 90%; other targets scale grown intervals by
+    /// `ln(target) / ln(0.9)`.
+    pub desired_retention: f64,
+}
+
+impl Default for Sm2Params {
+    fn default() -> Self {
+        Self {
+            ease_modifier: 1.0,
+            desired_retention: BASELINE_RETENTION,
+        }
+    }
+}
+
+impl Sm2Params {
+    /// Apply one SM-2 review with the given quality grade (clamped to 0..=5).
+    pub fn schedule(&self, state: &ReviewState, quality: i32, now: DateTime<Utc>) -> ReviewState {
+        let q = quality.clamp(0, 5);
+        let mut next = state.clone();
+
+        if q >= PASSING_QUALITY {
+            next.interval_days = match state.repetitions {
+                0 => FIRST_INTERVAL_DAYS,
+                1 => SECOND_INTERVAL_DAYS,
+                _ => (state.interval_days
+                    * state.ease_factor
+                    * self.ease_modifier
+                    * self.retention_factor())
+                .round()
+                .max(1.0),
+            };
+            next.repetitions = state.repetitions + 1;
+        } else {
+            next.interval_days = FIRST_INTERVAL_DAYS;
+            next.repetitions = 0;
+        }
+
+        next.ease_factor = adjusted_ease(state.ease_factor, q);
+        next.last_review = Some(now);
+        next.next_review = now + days_to_duration(next.interval_days);
+        next
+    }
+
+    /// How much grown intervals are stretched to hit `desired_retention`.
+    fn retention_factor(&self) -> f64 {
+        let target = self.desired_retention.clamp(0.5, 0.99);
+        target.ln() / BASELINE_RETENTION.ln()
+    }
+}
+
+/// Apply one SM-2 review with the default parameters.
+pub fn schedule(state: &ReviewState, quality: i32, now: DateTime<Utc>) -> ReviewState {
+    Sm2Params::default().schedule(state, quality, now)
+}
+
+/// SM-2 ease update: EF' = EF + (0.1 - (5 - q) * (0.08 + (5 - q) * 0.02)).
+pub fn adjusted_ease(ease: f64, quality: i32) -> f64 {
+    let miss = f64::from(5 - quality.clamp(0, 5));
+    (ease + 0.1 - miss * (0.08 + miss * 0.02)).max(MIN_EASE)
+}
+
+/// Convert a (possibly fractional) day count into a `chrono::Duration`.
+pub fn days_to_duration(days: f64) -> Duration {
+    Duration::seconds((days * 86_400.0).round() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn t0() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap()
+    }
+
+    /// Review `state` `n` times with the same grade, each review happening
+    /// exactly when the card falls due. Returns the interval after each step.
+    fn intervals(quality: i32, n: usize) -> Vec<f64> {
+        let mut state = ReviewState::new(1, t0());
+        let mut out = Vec::with_capacity(n);
+        for _ in 0..n {
+            let now = state.next_review;
+            state = schedule(&state, quality, now);
+            out.push(state.interval_days);
+        }
+        out
+    }
+
+    #[test]
+    fn good_answers_follow_classic_sequence() {
+        // Quality 4 keeps EF at 2.5: 1, 6, 15, 38, 95 days.
+        assert_eq!(intervals(4, 5), vec![1.0, 6.0, 15.0, 38.0, 95.0]);
+    }
+
+    #[test]
+    fn perfect_answers_grow_ease() {
+        // EF goes 2.5 -> 2.6 -> 2.7 -> 2.8; intervals use the EF before each update.
+        assert_eq!(intervals(5, 4), vec![1.0, 6.0, 16.0, 45.0]);
+
+        let mut state = ReviewState::new(1, t0());
+        for _ in 0..3 {
+            state = schedule(&state, 5, state.next_review);
+        }
+        assert!((state.ease_factor - 2.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn hard_answers_shrink_ease_down_to_floor() {
+        // Quality 3 drops EF by 0.14 per review until it hits MIN_EASE.
+        let intervals = intervals(3, 5);
+        assert_eq!(intervals, vec![1.0, 6.0, 13.0, 27.0, 52.0]);
+
+        let mut state = ReviewState::new(1, t0());
+        for _ in 0..20 {
+            state = schedule(&state, 3, state.next_review);
+        }
+        assert_eq!(state.ease_factor, MIN_EASE);
+    }
+
+    #[test]
+    fn lower_retention_target_lengthens_grown_intervals() {
+        let params = Sm2Params {
+            desired_retention: 0.8,
+            ..Sm2Params::default()
+        };
+        let mut state = ReviewState::new(1, t0());
+        let mut out = Vec::new();
+        for _ in 0..3 {
+            state = params.schedule(&state, 4, state.next_review);
+            out.push(state.interval_days);
+        }
+        // ln(0.8) / ln(0.9) ≈ 2.118, so 6 * 2.5 * 2.118 ≈ 31.8.
+        assert_eq!(out, vec![1.0, 6.0, 32.0]);
+    }
+
+    #[test]
+    fn lapse_resets_repetitions_and_interval() {
+        let mut state = ReviewState::new(1, t0());
+        for _ in 0..3 {
+            state = schedule(&state, 4, state.next_review);
+        }
+        assert_eq!(state.repetitions, 3);
+        assert_eq!(state.interval_days, 15.0);
+
+        let now = state.next_review;
+        let lapsed = schedule(&state, 1, now);
+        assert_eq!(lapsed.repetitions, 0);
+        assert_eq!(lapsed.interval_days, 1.0);
+        assert!((lapsed.ease_factor - 1.96).abs() < 1e-9);
+        assert_eq!(lapsed.next_review, now + Duration::days(1));
+
+        // After the lapse, the sequence restarts at 1 and 6 days.
+        let again = schedule(&lapsed, 4, lapsed.next_review);
+        assert_eq!(again.interval_days, 1.0);
+        let again = schedule(&again, 4, again.next_review);
+        assert_eq!(again.interval_days, 6.0);
+    }
+
+    #[test]
+    fn next_review_is_now_plus_interval() {
+        let now = t0();
+        let state = schedule(&ReviewState::new(7, now), 4, now);
+        assert_eq!(state.card_id, 7);
+        assert_eq!(state.next_review, now + Duration::days(1));
+
+        let later = now + Duration::days(1);
+        let state = schedule(&state, 4, later);
+        assert_eq!(state.next_review, later + Duration::days(6));
+    }
+
+    #[test]
+    fn quality_is_clamped() {
+        let now = t0();
+        let base = ReviewState::new(1, now);
+        let high = schedule(&base, 42, now);
+        let five = schedule(&base, 5, now);
+        assert_eq!(high.ease_factor, five.ease_factor);
+
+        let low = schedule(&base, -3, now);
+        let zero = schedule(&base, 0, now);
+        assert_eq!(low.ease_factor, zero.ease_factor);
+    }
+}
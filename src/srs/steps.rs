@@ -0,0 +1,264 @@
+// src/srs/steps.rs
+//
+// Learning / relearning steps.
+//
+// New cards walk through a short list of learning steps (e.g. 1m, 10m)
+// before they "graduate" to the day-based scheduler; cards that lapse
+// during review walk through relearning steps before returning to it.
+//
+//   New ──answer──▶ Learning ──last step / Easy──▶ Review
+//                                                  │  ▲
+//                                           Again  ▼  │ last step / Easy
+//                                              Relearning
+//
+// `SteppedScheduler` wraps any other `Scheduler` and handles the step
+// phases itself; the wrapped scheduler only sees graduations and reviews.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::model::{CardPhase, ReviewState};
+
+use super::{sm2::days_to_duration, Rating, Scheduler, SchedulerKind};
+
+/// Step delays in minutes (`1440` = one day).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LearningSteps {
+    pub learning: Vec<u32>,
+    pub relearning: Vec<u32>,
+}
+
+impl Default for LearningSteps {
+    fn default() -> Self {
+        Self {
+            learning: vec![1, 10],
+            relearning: vec![10],
+        }
+    }
+}
+
+/// Parse a step list such as `"1m 10m 1d"` (units: `m`, `h`, `d`; a bare
+/// number means minutes) into minutes. A blank list means no steps.
+pub fn parse_steps(text: &str) -> Result<Vec<u32>, String> {
+    text.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|s| !s.is_empty())
+        .map(|token| {
+            let token = token.to_ascii_lowercase();
+            let (num, mult) = match token.chars().last() {
+                Some('m') => (&token[..token.len() - 1], 1),
+                Some('h') => (&token[..token.len() - 1], 60),
+                Some('d') => (&token[..token.len() - 1], 1440),
+                _ => (token.as_str(), 1),
+            };
+            match num.parse::<u32>() {
+                Ok(n) if n > 0 => n
+                    .checked_mul(mult)
+                    .ok_or_else(|| format!("step '{token}' is too long")),
+                _ => Err(format!("invalid step '{token}'")),
+            }
+        })
+        .collect()
+}
+
+/// Inverse of `parse_steps`: `[1, 10, 1440]` → `"1m 10m 1d"`.
+pub fn format_steps(steps: &[u32]) -> String {
+    steps
+        .iter()
+        .map(|&m| {
+            if m % 1440 == 0 {
+                format!("{}d", m / 1440)
+            } else if m % 60 == 0 {
+                format!("{}h", m / 60)
+            } else {
+                format!("{m}m")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Adds learning and relearning steps in front of another scheduler.
+pub struct SteppedScheduler {
+    inner: Box<dyn Scheduler>,
+    steps: LearningSteps,
+}
+
+impl SteppedScheduler {
+    pub fn new(inner: Box<dyn Scheduler>, steps: LearningSteps) -> Self {
+        Self { inner, steps }
+    }
+
+    /// Move to step `step` of `steps`, due after that step's delay.
+    fn at_step(
+        state: &ReviewState,
+        phase: CardPhase,
+        steps: &[u32],
+        step: usize,
+        now: DateTime<Utc>,
+    ) -> ReviewState {
+        let mut next = state.clone();
+        next.phase = phase;
+        next.step = step as u32;
+        next.last_review = Some(now);
+        next.next_review = now + Duration::minutes(i64::from(steps[step]));
+        next
+    }
+
+    fn learn(&self, state: &ReviewState, rating: Rating, now: DateTime<Utc>) -> ReviewState {
+        let steps = &self.steps.learning;
+        let step = (state.step as usize).min(steps.len() - 1);
+
+        match rating {
+            Rating::Again => Self::at_step(state, CardPhase::Learning, steps, 0, now),
+            Rating::Hard => Self::at_step(state, CardPhase::Learning, steps, step, now),
+            Rating::Good if step + 1 < steps.len() => {
+                Self::at_step(state, CardPhase::Learning, steps, step + 1, now)
+            }
+            // Last step passed, or Easy: graduate to the day scheduler.
+            _ => {
+                let mut next = self.inner.review(state, rating, now);
+                next.phase = CardPhase::Review;
+                next.step = 0;
+                next
+            }
+        }
+    }
+
+    fn relearn(&self, state: &ReviewState, rating: Rating, now: DateTime<Utc>) -> ReviewState {
+        let steps = &self.steps.relearning;
+        let step = (state.step as usize).min(steps.len() - 1);
+
+        match rating {
+            Rating::Again => Self::at_step(state, CardPhase::Relearning, steps, 0, now),
+            Rating::Hard => Self::at_step(state, CardPhase::Relearning, steps, step, now),
+            Rating::Good if step + 1 < steps.len() => {
+                Self::at_step(state, CardPhase::Relearning, steps, step + 1, now)
+            }
+            // Back to review, using the interval the lapse already computed.
+            _ => {
+                let mut next = state.clone();
+                next.phase = CardPhase::Review;
+                next.step = 0;
+                next.last_review = Some(now);
+                next.next_review = now + days_to_duration(state.interval_days);
+                next
+            }
+        }
+    }
+}
+
+impl Scheduler for SteppedScheduler {
+    fn kind(&self) -> SchedulerKind {
+        self.inner.kind()
+    }
+
+    fn is_due(&self, state: &ReviewState, now: DateTime<Utc>) -> bool {
+        self.inner.is_due(state, now)
+    }
+
+    fn review(&self, state: &ReviewState, rating: Rating, now: DateTime<Utc>) -> ReviewState {
+        match state.phase {
+            CardPhase::New | CardPhase::Learning if !self.steps.learning.is_empty() => {
+                self.learn(state, rating, now)
+            }
+            CardPhase::Relearning if !self.steps.relearning.is_empty() => {
+                self.relearn(state, rating, now)
+            }
+            _ => {
+                let lapsed = rating == Rating::Again && state.phase == CardPhase::Review;
+                let mut next = self.inner.review(state, rating, now);
+                next.step = 0;
+                next.phase = CardPhase::Review;
+                if lapsed {
+                    next.lapses += 1;
+                }
+
+                if let (true, Some(&first)) = (lapsed, self.steps.relearning.first()) {
+                    // `next` already holds the post-lapse interval and memory
+                    // state; the card just comes back sooner first.
+                    next.phase = CardPhase::Relearning;
+                    next.next_review = now + Duration::minutes(i64::from(first));
+                }
+                next
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 5, 1, 9, 30, 0).unwrap()
+    }
+
+    fn stepped() -> SteppedScheduler {
+        SteppedScheduler::new(SchedulerKind::Sm2.build(), LearningSteps::default())
+    }
+
+    #[test]
+    fn steps_round_trip() {
+        let steps = parse_steps("1m 10m 1d").unwrap();
+        assert_eq!(steps, [1, 10, 1440]);
+        assert_eq!(format_steps(&steps), "1m 10m 1d");
+
+        assert_eq!(parse_steps("2h, 30").unwrap(), [120, 30]);
+        assert_eq!(parse_steps("  ").unwrap(), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn bad_steps_are_rejected() {
+        assert!(parse_steps("0").is_err());
+        assert!(parse_steps("1m x").is_err());
+        assert!(parse_steps("d").is_err());
+        assert!(parse_steps("-5m").is_err());
+        assert_eq!(
+            parse_steps("3000000d"),
+            Err("step '3000000d' is too long".to_string())
+        );
+    }
+
+    #[test]
+    fn new_card_walks_the_learning_steps_then_graduates() {
+        let scheduler = stepped();
+        let new = ReviewState::new(1, now());
+
+        let first = scheduler.review(&new, Rating::Good, now());
+        assert_eq!(first.phase, CardPhase::Learning);
+        assert_eq!(first.step, 1);
+        assert_eq!(first.next_review, now() + Duration::minutes(10));
+
+        let again = scheduler.review(&first, Rating::Again, now());
+        assert_eq!((again.phase, again.step), (CardPhase::Learning, 0));
+        assert_eq!(again.next_review, now() + Duration::minutes(1));
+
+        let graduated = scheduler.review(&first, Rating::Good, now());
+        assert_eq!((graduated.phase, graduated.step), (CardPhase::Review, 0));
+        assert!(graduated.next_review >= now() + Duration::days(1));
+
+        let easy = scheduler.review(&new, Rating::Easy, now());
+        assert_eq!(easy.phase, CardPhase::Review);
+    }
+
+    #[test]
+    fn lapse_relearns_then_returns_to_review() {
+        let scheduler = stepped();
+        let new = ReviewState::new(1, now());
+        let review = scheduler.review(&new, Rating::Easy, now());
+        let later = review.next_review;
+
+        let lapsed = scheduler.review(&review, Rating::Again, later);
+        assert_eq!(lapsed.phase, CardPhase::Relearning);
+        assert_eq!(lapsed.lapses, 1);
+        assert_eq!(lapsed.next_review, later + Duration::minutes(10));
+
+        let back = scheduler.review(&lapsed, Rating::Good, lapsed.next_review);
+        assert_eq!((back.phase, back.lapses), (CardPhase::Review, 1));
+        assert_eq!(
+            back.next_review,
+            lapsed.next_review + days_to_duration(lapsed.interval_days)
+        );
+    }
+}
@@ -0,0 +1,127 @@
+// src/srs/text.rs
+//
+// Small text helpers shared by answer options and grading: edit distance,
+// accent folding and a rough guess at which writing system a string uses.
+
+/// Base letters for U+00C0..=U+017F (Latin-1 Supplement letters and Latin
+/// Extended-A), index = code point - 0xC0. Letters without a plain base
+/// (Æ, ß, Þ, ...) map to themselves.
+const LATIN_BASE: &str = "AAAAAAÆCEEEEIIIIDNOOOOO×OUUUUYÞßaaaaaaæceeeeiiiidnooooo÷ouuuuyþy\
+AaAaAaCcCcCcCcDdDdEeEeEeEeEeGgGgGgGgHhHhIiIiIiIiIiIiJjKkkLlLlLlLlLlNnNnNnnNnOoOoOoŒœRrRrRrSsSsSsSsTtTtTtUuUuUuUuUuUuWwYyYZzZzZzs";
+
+/// Writing system a string is (mostly) written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    Arabic,
+    Hebrew,
+    Devanagari,
+    Kana,
+    Han,
+    Hangul,
+    /// Digits, punctuation or anything not listed above.
+    Other,
+}
+
+impl Script {
+    fn of_char(c: char) -> Option<Script> {
+        let script = match c as u32 {
+            0x41..=0x5A | 0x61..=0x7A | 0xC0..=0x24F | 0x1E00..=0x1EFF => Script::Latin,
+            0x370..=0x3FF | 0x1F00..=0x1FFF => Script::Greek,
+            0x400..=0x52F => Script::Cyrillic,
+            0x590..=0x5FF => Script::Hebrew,
+            0x600..=0x6FF | 0x750..=0x77F => Script::Arabic,
+            0x900..=0x97F => Script::Devanagari,
+            0x3040..=0x30FF => Script::Kana,
+            0x4E00..=0x9FFF | 0x3400..=0x4DBF => Script::Han,
+            0xAC00..=0xD7AF | 0x1100..=0x11FF => Script::Hangul,
+            _ => return None,
+        };
+        Some(script)
+    }
+
+    /// The script most letters of `text` belong to.
+    pub fn detect(text: &str) -> Script {
+        let mut counts: Vec<(Script, usize)> = Vec::new();
+        for script in text.chars().filter_map(Script::of_char) {
+            match counts.iter_mut().find(|(s, _)| *s == script) {
+                Some((_, n)) => *n += 1,
+                None => counts.push((script, 1)),
+            }
+        }
+        counts
+            .into_iter()
+            .max_by_key(|&(_, n)| n)
+            .map_or(Script::Other, |(s, _)| s)
+    }
+}
+
+/// `c` without its accent (`é` -> `e`, `Ł` -> `L`). Combining marks
+/// (U+0300..=U+036F) return `None` so decomposed text folds the same way.
+pub fn fold_diacritic(c: char) -> Option<char> {
+    match c as u32 {
+        0x300..=0x36F => None,
+        code @ 0xC0..=0x17F => LATIN_BASE.chars().nth((code - 0xC0) as usize),
+        _ => Some(c),
+    }
+}
+
+/// Levenshtein distance between `a` and `b`, counted in chars.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        cur[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitute = prev[j] + usize::from(ca != cb);
+            cur[j + 1] = substitute.min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+
+    prev[b.len()]
+}
+
+/// Edit-distance similarity in `0.0..=1.0` (1.0 = identical).
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(a, b) as f64 / longest as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levenshtein_counts_chars_not_bytes() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("café", "cafe"), 1);
+        assert_eq!(levenshtein("猫", "犬"), 1);
+    }
+
+    #[test]
+    fn detects_the_dominant_script() {
+        assert_eq!(Script::detect("bonjour"), Script::Latin);
+        assert_eq!(Script::detect("привет!"), Script::Cyrillic);
+        assert_eq!(Script::detect("ひらがな"), Script::Kana);
+        assert_eq!(Script::detect("42"), Script::Other);
+    }
+
+    #[test]
+    fn folds_accents_in_both_latin_blocks() {
+        assert_eq!(LATIN_BASE.chars().count(), 0x180 - 0xC0);
+        let fold = |s: &str| s.chars().filter_map(fold_diacritic).collect::<String>();
+        assert_eq!(fold("Crème brûlée"), "Creme brulee");
+        assert_eq!(fold("Łódź"), "Lodz");
+        assert_eq!(fold("e\u{301}te\u{301}"), "ete");
+        assert_eq!(fold("straße"), "straße");
+    }
+}
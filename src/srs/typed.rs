@@ -0,0 +1,310 @@
+// src/srs/typed.rs
+//
+// Grading typed answers.
+//
+// The grading modes are progressively more lenient: each one also accepts
+// whatever the stricter modes before it accept. Leading / trailing spaces
+// never count.
+
+use super::text;
+use super::{AutoGrade, Rating};
+
+/// Seconds of typing allowed per expected character before the response
+/// time starts to count against the rating.
+const TYPING_SECS_PER_CHAR: f32 = 0.3;
+
+/// Longest answer (in characters, either side) `diff` lines up character
+/// by character; its table grows with the product of the two lengths.
+pub const MAX_DIFF_CHARS: usize = 256;
+
+/// How strictly a typed answer has to match.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TypedGrading {
+    /// Character for character.
+    Exact,
+    /// Upper / lower case doesn't matter.
+    IgnoreCase,
+    /// Case and accents don't matter (`ecole` for `École`).
+    #[default]
+    IgnoreDiacritics,
+    /// As `IgnoreDiacritics`, plus a few typos are forgiven (scored as
+    /// `Hard`).
+    Tolerant,
+}
+
+impl TypedGrading {
+    pub const ALL: [TypedGrading; 4] = [
+        TypedGrading::Exact,
+        TypedGrading::IgnoreCase,
+        TypedGrading::IgnoreDiacritics,
+        TypedGrading::Tolerant,
+    ];
+
+    /// Human-readable name for options screens.
+    pub fn label(self) -> &'static str {
+        match self {
+            TypedGrading::Exact => "Exact",
+            TypedGrading::IgnoreCase => "Ignore case",
+            TypedGrading::IgnoreDiacritics => "Ignore case & accents",
+            TypedGrading::Tolerant => "Allow typos",
+        }
+    }
+
+    /// The form `c` is compared in (`None` = ignored entirely).
+    fn key(self, c: char) -> Option<char> {
+        match self {
+            TypedGrading::Exact => Some(c),
+            TypedGrading::IgnoreCase => c.to_lowercase().next(),
+            TypedGrading::IgnoreDiacritics | TypedGrading::Tolerant => {
+                text::fold_diacritic(c).and_then(|c| c.to_lowercase().next())
+            }
+        }
+    }
+
+    fn normalize(self, s: &str) -> String {
+        s.trim().chars().filter_map(|c| self.key(c)).collect()
+    }
+}
+
+/// Outcome of checking one typed answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypedVerdict {
+    /// Matches under the grading mode.
+    Correct,
+    /// Within the typo allowance (`TypedGrading::Tolerant` only).
+    Close {
+        typos: usize,
+    },
+    Wrong,
+}
+
+impl TypedVerdict {
+    pub fn is_accepted(self) -> bool {
+        self != TypedVerdict::Wrong
+    }
+
+    /// Scheduler rating: a correct answer is graded by response time
+    /// (less an allowance for typing `expected_len` characters), a close
+    /// one is `Hard` and a wrong one `Again`.
+    pub fn rating(self, auto_grade: &AutoGrade, response_secs: f32, expected_len: usize) -> Rating {
+        match self {
+            TypedVerdict::Correct => {
+                let thinking = response_secs - TYPING_SECS_PER_CHAR * expected_len as f32;
+                auto_grade.rate(true, thinking.max(0.0))
+            }
+            TypedVerdict::Close { .. } => Rating::Hard,
+            TypedVerdict::Wrong => Rating::Again,
+        }
+    }
+}
+
+/// Grades typed answers against the card's term.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypedGrader {
+    pub mode: TypedGrading,
+    /// Most typos forgiven in `Tolerant` mode. Short answers get fewer:
+    /// at most one per four letters.
+    pub max_typos: usize,
+}
+
+impl Default for TypedGrader {
+    fn default() -> Self {
+        Self {
+            mode: TypedGrading::default(),
+            max_typos: 2,
+        }
+    }
+}
+
+impl TypedGrader {
+    pub fn check(&self, typed: &str, expected: &str) -> TypedVerdict {
+        let typed = self.mode.normalize(typed);
+        let expected = self.mode.normalize(expected);
+
+        if typed.is_empty() {
+            return TypedVerdict::Wrong;
+        }
+        if typed == expected {
+            return TypedVerdict::Correct;
+        }
+        if self.mode == TypedGrading::Tolerant {
+            let allowed = self.max_typos.min(expected.chars().count() / 4);
+            let typos = text::levenshtein(&typed, &expected);
+            if typos <= allowed {
+                return TypedVerdict::Close { typos };
+            }
+        }
+        TypedVerdict::Wrong
+    }
+}
+
+/// How one character of a typed answer lines up with the expected one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    /// Typed and expected agree here.
+    Same,
+    /// Typed, but not in the expected answer.
+    Extra,
+    /// In the expected answer, but not typed.
+    Missing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffChar {
+    pub ch: char,
+    pub kind: DiffKind,
+}
+
+/// Character-level diff of `typed` against `expected` (longest common
+/// subsequence), comparing characters the way `mode` does. `Same`
+/// characters are shown as typed; `Missing` ones as expected. Past
+/// `MAX_DIFF_CHARS` on either side, the whole expected answer is given as
+/// `Missing` instead.
+pub fn diff(typed: &str, expected: &str, mode: TypedGrading) -> Vec<DiffChar> {
+    let keyed = |s: &str| -> Vec<(char, char)> {
+        s.trim()
+            .chars()
+            .filter_map(|c| mode.key(c).map(|k| (c, k)))
+            .collect()
+    };
+    let a = keyed(typed);
+    let b = keyed(expected);
+
+    if a.len() > MAX_DIFF_CHARS || b.len() > MAX_DIFF_CHARS {
+        return expected
+            .trim()
+            .chars()
+            .map(|ch| DiffChar {
+                ch,
+                kind: DiffKind::Missing,
+            })
+            .collect();
+    }
+
+    // lcs[i][j] = LCS length of a[i..] and b[j..].
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i].1 == b[j].1 {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = Vec::with_capacity(a.len().max(b.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        let kind = if i < a.len() && j < b.len() && a[i].1 == b[j].1 {
+            DiffKind::Same
+        } else if j < b.len() && (i == a.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            DiffKind::Missing
+        } else {
+            DiffKind::Extra
+        };
+
+        let ch = match kind {
+            DiffKind::Same | DiffKind::Extra => a[i].0,
+            DiffKind::Missing => b[j].0,
+        };
+        out.push(DiffChar { ch, kind });
+
+        match kind {
+            DiffKind::Same => {
+                i += 1;
+                j += 1;
+            }
+            DiffKind::Extra => i += 1,
+            DiffKind::Missing => j += 1,
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grader(mode: TypedGrading) -> TypedGrader {
+        TypedGrader {
+            mode,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn modes_get_progressively_more_lenient() {
+        let cases = [
+            ("École", "École"),
+            ("école", "École"),
+            ("ecole", "École"),
+            ("ecolle", "École"),
+        ];
+        let accepted = |mode| {
+            cases
+                .iter()
+                .filter(|(typed, expected)| grader(mode).check(typed, expected).is_accepted())
+                .count()
+        };
+
+        assert_eq!(accepted(TypedGrading::Exact), 1);
+        assert_eq!(accepted(TypedGrading::IgnoreCase), 2);
+        assert_eq!(accepted(TypedGrading::IgnoreDiacritics), 3);
+        assert_eq!(accepted(TypedGrading::Tolerant), 4);
+    }
+
+    #[test]
+    fn typo_allowance_shrinks_for_short_answers() {
+        let g = grader(TypedGrading::Tolerant);
+        assert_eq!(g.check("cta", "cat"), TypedVerdict::Wrong);
+        assert_eq!(g.check("hous", "house"), TypedVerdict::Close { typos: 1 });
+        assert_eq!(g.check("hose", "horse"), TypedVerdict::Close { typos: 1 });
+        assert_eq!(g.check("hoarse", "horse"), TypedVerdict::Close { typos: 1 });
+        assert_eq!(
+            g.check("ecyclopdia", "encyclopedia"),
+            TypedVerdict::Close { typos: 2 }
+        );
+        assert_eq!(g.check("", "house"), TypedVerdict::Wrong);
+    }
+
+    #[test]
+    fn close_answers_rate_hard_and_slow_typing_is_not_penalised() {
+        let auto = AutoGrade::default();
+        assert_eq!(
+            TypedVerdict::Close { typos: 1 }.rating(&auto, 1.0, 5),
+            Rating::Hard
+        );
+        assert_eq!(TypedVerdict::Wrong.rating(&auto, 1.0, 5), Rating::Again);
+        // 12 characters at 0.3 s each leave 3.4 s of thinking: Good, not Hard.
+        assert_eq!(TypedVerdict::Correct.rating(&auto, 7.0, 12), Rating::Good);
+    }
+
+    #[test]
+    fn diff_marks_extra_and_missing_characters() {
+        let render = |d: &[DiffChar]| -> String {
+            d.iter()
+                .map(|c| match c.kind {
+                    DiffKind::Same => c.ch.to_string(),
+                    DiffKind::Extra => format!("-{}", c.ch),
+                    DiffKind::Missing => format!("+{}", c.ch),
+                })
+                .collect()
+        };
+
+        let d = diff("hoarse", "horse", TypedGrading::Exact);
+        assert_eq!(render(&d), "ho-arse");
+
+        let d = diff("ecole", "École", TypedGrading::IgnoreCase);
+        assert_eq!(render(&d), "+É-ecole");
+    }
+
+    #[test]
+    fn overlong_answers_show_the_expected_text() {
+        let pasted = "word ".repeat(MAX_DIFF_CHARS);
+        let d = diff(&pasted, " a small pet ", TypedGrading::Exact);
+        let expected: String = d.iter().map(|c| c.ch).collect();
+        assert_eq!(expected, "a small pet");
+        assert!(d.iter().all(|c| c.kind == DiffKind::Missing));
+    }
+}